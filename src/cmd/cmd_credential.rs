@@ -0,0 +1,42 @@
+use clap::{Arg, Command};
+
+pub fn new_credential_cmd() -> Command {
+    clap::Command::new("credential")
+        .about("local credential profiles referenced by 'profile:<name>' in task definitions")
+        .subcommand(credential_add())
+        .subcommand(credential_remove())
+        .subcommand(credential_list())
+}
+
+fn credential_add() -> Command {
+    clap::Command::new("add")
+        .about("add or replace credential profile")
+        .args(&[Arg::new("name")
+            .value_name("name")
+            .required(true)
+            .index(1)
+            .help("profile name")])
+        .args(&[Arg::new("access_key_id")
+            .long("access-key-id")
+            .value_name("access_key_id")
+            .required(true)
+            .help("object storage access key id")])
+        .args(&[Arg::new("secret_access_key")
+            .long("secret-access-key")
+            .value_name("secret")
+            .help("object storage secret access key, read from stdin if absent to keep it out of shell history")])
+}
+
+fn credential_remove() -> Command {
+    clap::Command::new("remove")
+        .about("remove credential profile")
+        .args(&[Arg::new("name")
+            .value_name("name")
+            .required(true)
+            .index(1)
+            .help("profile name")])
+}
+
+fn credential_list() -> Command {
+    clap::Command::new("list").about("list credential profiles with masked secrets")
+}
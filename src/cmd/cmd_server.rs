@@ -1,4 +1,4 @@
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 
 pub fn new_server_cmd() -> Command {
     clap::Command::new("server")
@@ -22,26 +22,59 @@ fn server_add() -> Command {
             .required(true)
             .index(2)
             .help("server url")])
+        .args(&[Arg::new("bearer_token")
+            .long("bearer-token")
+            .value_name("token")
+            .conflicts_with("basic_auth")
+            .help("bearer token sent in Authorization header")])
+        .args(&[Arg::new("basic_auth")
+            .long("basic-auth")
+            .value_name("user:password")
+            .help("basic auth user and password")])
+        .args(&[Arg::new("header")
+            .long("header")
+            .short('H')
+            .value_name("name: value")
+            .action(ArgAction::Append)
+            .help("custom header sent with every request, can be repeated")])
+        .args(&[Arg::new("ca_cert")
+            .long("ca-cert")
+            .value_name("file")
+            .help("pem encoded ca bundle used to verify https server")])
+        .args(&[Arg::new("client_cert")
+            .long("client-cert")
+            .value_name("file")
+            .requires("client_key")
+            .help("pem encoded client certificate for mutual tls")])
+        .args(&[Arg::new("client_key")
+            .long("client-key")
+            .value_name("file")
+            .requires("client_cert")
+            .help("pem encoded PKCS#8 private key of client certificate")])
+        .args(&[Arg::new("insecure")
+            .long("insecure")
+            .action(ArgAction::SetTrue)
+            .help("skip server certificate verification, for test environments only")])
 }
 
 fn server_set() -> Command {
     clap::Command::new("set")
         .about("set current server")
         .args(&[Arg::new("server_id")
-            .value_name("server_id")
+            .value_name("server")
             .required(true)
             .index(1)
-            .help("analyze source objects destributed")])
+            .help("server id or name")])
 }
 
 fn server_remove() -> Command {
     clap::Command::new("remove")
         .about("remove_server")
         .args(&[Arg::new("server_id")
-            .value_name("server_id")
+            .value_name("server")
             .required(true)
             .index(1)
-            .help("analyze source objects destributed")])
+            .help("server id or name")])
 }
 
 fn server_list() -> Command {
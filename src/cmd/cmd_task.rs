@@ -1,29 +1,39 @@
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 
 pub fn new_task_cmd() -> Command {
     clap::Command::new("task")
         .subcommand(task_show())
         .subcommand(task_create())
         .subcommand(task_update())
+        .subcommand(task_clone())
         .subcommand(task_remove())
         .subcommand(task_clean())
         .subcommand(task_start())
         .subcommand(task_stop())
         .subcommand(task_checkpoint())
         .subcommand(task_status())
+        .subcommand(task_watch())
         .subcommand(task_analyze())
+        .subcommand(task_preview())
+        .subcommand(task_validate())
         .subcommand(task_list_all())
+        .subcommand(task_export())
+        .subcommand(task_import())
         .subcommand(task_all_living())
 }
 
 fn task_show() -> Command {
     clap::Command::new("show")
-        .about("show task description")
+        .about("show task description, credentials are masked")
         .args(&[Arg::new("taskid")
             .value_name("taskid")
             .required(true)
             .index(1)
             .help("analyze source objects destributed")])
+        .args(&[Arg::new("show_secrets")
+            .long("show-secrets")
+            .action(ArgAction::SetTrue)
+            .help("print credentials in plain text")])
 }
 
 fn task_create() -> Command {
@@ -31,14 +41,27 @@ fn task_create() -> Command {
         .about("create task")
         .args(&[Arg::new("taskjson")
             .value_name("taskjson")
-            .required(true)
+            .required_unless_present("file")
+            .conflicts_with("file")
             .index(1)
             .help("create task with json")])
+        .args(&[Arg::new("file")
+            .short('f')
+            .long("file")
+            .value_name("file")
+            .help(
+                "json or yaml task file, multi-document yaml creates several tasks, '-' for stdin",
+            )])
+        .args(&[Arg::new("yes")
+            .short('y')
+            .long("yes")
+            .action(ArgAction::SetTrue)
+            .help("skip confirmation for destructive truncate bucket task")])
 }
 
 fn task_update() -> Command {
     clap::Command::new("update")
-        .about("create task")
+        .about("update task with full definition, merge patch or property overrides")
         .args(&[Arg::new("taskid")
             .value_name("taskid")
             .required(true)
@@ -46,49 +69,115 @@ fn task_update() -> Command {
             .help("specify task id")])
         .args(&[Arg::new("taskjson")
             .value_name("taskjson")
-            .required(true)
-            .index(1)
+            .required_unless_present_any(["file", "set", "patch"])
+            .conflicts_with_all(["file", "set", "patch"])
+            .index(2)
             .help("new task json")])
+        .args(&[Arg::new("file")
+            .short('f')
+            .long("file")
+            .value_name("file")
+            .conflicts_with_all(["set", "patch"])
+            .help("json or yaml task file, '-' for stdin")])
+        .args(&[Arg::new("patch")
+            .long("patch")
+            .value_name("file")
+            .help("RFC 7396 json merge patch file, yaml supported, '-' for stdin")])
+        .args(&[Arg::new("set")
+            .long("set")
+            .value_name("path=value")
+            .action(ArgAction::Append)
+            .help("override task property by dotted path, applied after --patch")])
+        .args(&[Arg::new("yes")
+            .short('y')
+            .long("yes")
+            .action(ArgAction::SetTrue)
+            .help("skip confirmation of task changes")])
 }
 
-fn task_remove() -> Command {
-    clap::Command::new("remove")
-        .about("remove task")
+fn task_clone() -> Command {
+    clap::Command::new("clone")
+        .about("create a new task from an existing task with overrides")
         .args(&[Arg::new("taskid")
             .value_name("taskid")
             .required(true)
             .index(1)
-            .help("remove task by task id")])
+            .help("task id to clone")])
+        .args(&[Arg::new("name")
+            .long("name")
+            .value_name("name")
+            .help("name of the new task")])
+        .args(&[Arg::new("set")
+            .long("set")
+            .value_name("path=value")
+            .action(ArgAction::Append)
+            .help("override task property by dotted path, e.g. target.prefix=new/")])
+        .args(&[Arg::new("yes")
+            .short('y')
+            .long("yes")
+            .action(ArgAction::SetTrue)
+            .help("skip confirmation for destructive truncate bucket task")])
+}
+
+fn task_remove() -> Command {
+    clap::Command::new("remove")
+        .about("remove task, multiple task ids or selectors for bulk operation")
+        .args(task_selector_args())
+        .args(&[Arg::new("yes")
+            .short('y')
+            .long("yes")
+            .action(ArgAction::SetTrue)
+            .help("skip confirmation when more than one task selected")])
 }
 
 fn task_clean() -> Command {
     clap::Command::new("clean")
-        .about("clean task")
-        .args(&[Arg::new("taskid")
-            .value_name("taskid")
-            .required(true)
-            .index(1)
-            .help("create task with json")])
+        .about("clean task, multiple task ids or selectors for bulk operation")
+        .args(task_selector_args())
+        .args(&[Arg::new("yes")
+            .short('y')
+            .long("yes")
+            .action(ArgAction::SetTrue)
+            .help("skip confirmation when more than one task selected")])
 }
 
 fn task_start() -> Command {
     clap::Command::new("start")
-        .about("start task")
-        .args(&[Arg::new("taskid")
-            .value_name("taskid")
-            .required(true)
-            .index(1)
-            .help("create task with json")])
+        .about("start task, multiple task ids or selectors for bulk operation")
+        .args(task_selector_args())
 }
 
 fn task_stop() -> Command {
     clap::Command::new("stop")
-        .about("stop task")
-        .args(&[Arg::new("taskid")
+        .about("stop task, multiple task ids or selectors for bulk operation")
+        .args(task_selector_args())
+}
+
+// 批量操作的任务选择参数，--name-regex 与 --type 可与任务 id 组合在其范围内筛选
+fn task_selector_args() -> [Arg; 4] {
+    [
+        Arg::new("taskid")
             .value_name("taskid")
-            .required(true)
+            .num_args(1..)
+            .action(ArgAction::Append)
+            .required_unless_present_any(["all", "name_regex", "type"])
             .index(1)
-            .help("create task with json")])
+            .help("one or more task ids"),
+        Arg::new("all")
+            .long("all")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("taskid")
+            .help("select all tasks on server"),
+        Arg::new("name_regex")
+            .long("name-regex")
+            .value_name("regex")
+            .help("select tasks whose name matches regex"),
+        Arg::new("type")
+            .long("type")
+            .value_name("type")
+            .value_parser(["transfer", "compare", "truncatebucket"])
+            .help("select tasks by task type"),
+    ]
 }
 
 fn task_analyze() -> Command {
@@ -96,15 +185,88 @@ fn task_analyze() -> Command {
         .about("analyze source objects destributed")
         .args(&[Arg::new("taskid")
             .value_name("taskid")
-            .required(true)
+            .required_unless_present("local")
             .index(1)
             .help("analyze source objects destributed")])
+        .args(&[Arg::new("local")
+            .long("local")
+            .value_name("task_file")
+            .conflicts_with("taskid")
+            .help("analyze local source of transfer task json or yaml file without server")])
+        .args(&[Arg::new("chart")
+            .long("chart")
+            .action(ArgAction::SetTrue)
+            .help("print ascii bar chart of size distribution")])
+}
+
+fn task_preview() -> Command {
+    clap::Command::new("preview")
+        .about("preview local files selected by transfer task without server")
+        .args(&[Arg::new("task_file")
+            .value_name("task_file")
+            .required(true)
+            .index(1)
+            .help("transfer task json or yaml file, '-' for stdin")])
+        .args(&[Arg::new("out_file")
+            .long("out-file")
+            .value_name("file")
+            .help("write selected keys to file instead of stdout")])
+}
+
+fn task_validate() -> Command {
+    clap::Command::new("validate")
+        .about("validate task json or yaml file without server")
+        .args(&[Arg::new("task_file")
+            .value_name("task_file")
+            .required(true)
+            .index(1)
+            .help("task json or yaml file, multi-document yaml supported, '-' for stdin")])
 }
 
 fn task_list_all() -> Command {
     clap::Command::new("list_all").about("list_all")
 }
 
+fn task_export() -> Command {
+    clap::Command::new("export")
+        .about("export task definitions to a versioned bundle file")
+        .args(task_selector_args())
+        .args(&[Arg::new("file")
+            .short('f')
+            .long("file")
+            .value_name("file")
+            .help(
+                "write bundle to file instead of stdout, json if file ends with .json or -o json",
+            )])
+        .args(&[Arg::new("show_secrets")
+            .long("show-secrets")
+            .action(ArgAction::SetTrue)
+            .help("allow printing a bundle with plain text credentials to stdout")])
+}
+
+fn task_import() -> Command {
+    clap::Command::new("import")
+        .about("create tasks from an exported bundle file")
+        .args(&[Arg::new("bundle")
+            .value_name("bundle")
+            .required(true)
+            .index(1)
+            .help("yaml or json bundle file, '-' for stdin")])
+        .args(&[Arg::new("new_ids")
+            .long("new-ids")
+            .action(ArgAction::SetTrue)
+            .help("generate new ids for all imported tasks")])
+        .args(&[Arg::new("meta_dir")
+            .long("meta-dir")
+            .value_name("dir")
+            .help("rewrite meta_dir of imported tasks to <dir>/<task_id>")])
+        .args(&[Arg::new("yes")
+            .short('y')
+            .long("yes")
+            .action(ArgAction::SetTrue)
+            .help("skip confirmation for destructive truncate bucket task")])
+}
+
 fn task_status() -> Command {
     clap::Command::new("status")
         .about("show task status")
@@ -115,6 +277,22 @@ fn task_status() -> Command {
             .help("analyze source objects destributed")])
 }
 
+fn task_watch() -> Command {
+    clap::Command::new("watch")
+        .about("watch task progress until task stopped")
+        .args(&[Arg::new("taskid")
+            .value_name("taskid")
+            .required(true)
+            .index(1)
+            .help("task id")])
+        .args(&[Arg::new("interval")
+            .long("interval")
+            .value_name("seconds")
+            .default_value("2")
+            .value_parser(clap::value_parser!(u64).range(1..))
+            .help("status polling interval in seconds")])
+}
+
 fn task_checkpoint() -> Command {
     clap::Command::new("checkpoint")
         .about("show task checkpoint")
@@ -122,7 +300,7 @@ fn task_checkpoint() -> Command {
             .value_name("taskid")
             .required(true)
             .index(1)
-            .help("analyze source objects destributed")])
+            .help("specify task id")])
 }
 
 fn task_all_living() -> Command {
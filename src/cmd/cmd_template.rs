@@ -1,8 +1,24 @@
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 
 pub fn new_template() -> Command {
     clap::Command::new("template")
         .about("generate oss task description yaml template")
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("format")
+                .global(true)
+                .value_parser(["json", "yaml"])
+                .default_value("json")
+                .help("template output format"),
+        )
+        .arg(
+            Arg::new("offline")
+                .long("offline")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("generate template locally without server"),
+        )
         .subcommand(template_transfer())
         .subcommand(truncate_bucket())
         .subcommand(compare())
@@ -58,7 +74,7 @@ fn template_transfer_local2local() -> Command {
 
 fn truncate_bucket() -> Command {
     clap::Command::new("truncate_bucket")
-        .about("generate oss task description template for local to local task")
+        .about("generate oss task description template for truncate bucket task")
         .args(&[Arg::new("file")
             .value_name("file")
             .required(false)
@@ -68,7 +84,7 @@ fn truncate_bucket() -> Command {
 
 fn compare() -> Command {
     clap::Command::new("compare")
-        .about("generate oss task description template for local to local task")
+        .about("generate oss task description template for compare task")
         .args(&[Arg::new("file")
             .value_name("file")
             .required(false)
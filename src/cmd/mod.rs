@@ -1,4 +1,5 @@
 mod cmd_config;
+mod cmd_credential;
 mod cmd_exit;
 mod cmd_gen_file;
 mod cmd_parameters;
@@ -1,27 +1,52 @@
+use crate::cmd::cmd_credential::new_credential_cmd;
 use crate::cmd::cmd_gen_file::{new_gen_file_cmd, new_gen_files_cmd};
 use crate::cmd::cmd_server::new_server_cmd;
 use crate::cmd::cmd_task::new_task_cmd;
 use crate::cmd::{
     new_command_tree_cmd, new_config_cmd, new_exit_cmd, new_parameters_cmd, new_template,
 };
+use crate::commons::yamlutile::{struct_to_yaml_string, yaml_documents_to_structs};
 use crate::commons::{
-    byte_size_str_to_usize, generate_file, generate_files, struct_to_json_string_prettry, SubCmd,
+    analyze_folder_files_size, byte_size_str_to_usize, byte_size_usize_to_str, format_output,
+    generate_file, generate_files, read_file_or_stdin, size_distributed_table,
+    struct_to_json_string_prettry, timestamp_to_string, walk_folder_files, FolderFilesAnalysis,
+    OutputFormat, SubCmd, OUTPUT_FORMATS,
+};
+use crate::commons::{
+    format_diff, json_to_struct, parse_path_assignment, CommandCompleter, DiffLine,
 };
-use crate::commons::{json_to_struct, CommandCompleter};
 use crate::configure::{generate_default_config, set_config_file_path};
 use crate::configure::{get_config_file_path, get_current_config_yml, set_config};
+use crate::exception::{exit_code, CliError, ClientError};
 use crate::interact;
 use crate::interact::INTERACT_STATUS;
 use crate::request::{
-    list_all_tasks, set_current_server, task_clean, task_create, task_remove, task_show,
-    task_start, task_status, task_stop, task_update, template_transfer_local2local,
-    template_transfer_local2oss, template_transfer_oss2local, template_transfer_oss2oss,
-    test_reqwest, ReqTaskUpdate, Task, TaskId, TaskServer, GLOBAL_CURRENT_SERVER, GLOBAL_RUNTIME,
+    clone_task, has_plain_secrets, list_all_tasks, mask_access_key_id, mask_secret,
+    mask_task_secrets, override_current_server, parse_basic, parse_header, patch_task,
+    prepare_import, resolve_task_secrets, set_current_server, task_all_living, task_analyze,
+    task_checkpoint, task_create, task_diff, task_show, task_status, task_yaml_with_comments,
+    template_compare, template_transfer_local2local, template_transfer_local2oss,
+    template_transfer_oss2local, template_transfer_oss2oss, template_truncate_bucket,
+    validate_task, ApiClient, BulkOp, BulkResult, CredentialProfile, ImportOptions, ObjectStorage,
+    OfflineTemplate, ReqTaskUpdate, ServerAuth, ServerTls, Task, TaskBundle, TaskId, TaskSelector,
+    TaskServer, TaskStatus, TaskStopReason, TransferTask, GLOBAL_CURRENT_SERVER, GLOBAL_RUNTIME,
+    SERVER_ENV,
+};
+use crate::resources::{
+    find_task_server, get_credential_profile, list_credential_profiles, list_servers_from_cf,
+    remove_credential_profile, remove_server_from_cf, save_credential_profile,
+    save_task_server_to_cf,
 };
-use crate::resources::{list_servers_from_cf, remove_server_from_cf, save_task_server_to_cf};
 use crate::tui::tui_start;
-use clap::{Arg, ArgAction, ArgMatches, Command as Clap_Command};
+use anyhow::{anyhow, Result};
+use clap::{parser::ValueSource, Arg, ArgAction, ArgMatches, Command as Clap_Command};
+use indicatif::{FormattedDuration, ProgressBar, ProgressStyle};
 use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tabled::builder::Builder;
 
 pub const APP_NAME: &'static str = "files_pipe_cli";
@@ -39,6 +64,22 @@ lazy_static! {
                 .value_name("FILE")
                 .help("Sets a custom config file")
         )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FORMAT")
+                .global(true)
+                .value_parser(OUTPUT_FORMATS)
+                .help("output format of read commands, default depends on command")
+        )
+        .arg(
+            Arg::new("server")
+                .long("server")
+                .value_name("SERVER")
+                .global(true)
+                .help("server id, name or url used by this invocation, overrides FILES_PIPE_SERVER env")
+        )
         .arg(
             Arg::new("ui")
                 .short('u')
@@ -54,6 +95,7 @@ lazy_static! {
                 .help("run as interact mod")
         )
         .subcommand(new_server_cmd())
+        .subcommand(new_credential_cmd())
         .subcommand(new_task_cmd())
         .subcommand(new_template())
         .subcommand(new_parameters_cmd())
@@ -68,13 +110,19 @@ lazy_static! {
 pub fn run_app() {
     set_config("");
     let matches = CLIAPP.clone().get_matches();
-    cmd_match(&matches);
+    if let Err(e) = cmd_match(&matches) {
+        eprintln!("error: {}", e);
+        std::process::exit(exit_code(&e));
+    }
 }
 
+// 交互模式下错误只输出，不退出进程
 pub fn run_from(args: Vec<String>) {
     match Clap_Command::try_get_matches_from(CLIAPP.to_owned(), args.clone()) {
         Ok(matches) => {
-            cmd_match(&matches);
+            if let Err(e) = cmd_match(&matches) {
+                eprintln!("error: {}", e);
+            }
         }
         Err(err) => {
             err.print().expect("Error writing Error");
@@ -127,7 +175,7 @@ fn subcommands() -> Vec<SubCmd> {
     subcmds
 }
 
-fn cmd_match(matches: &ArgMatches) {
+fn cmd_match(matches: &ArgMatches) -> Result<()> {
     if let Some(c) = matches.get_one::<String>("config") {
         set_config_file_path(c.to_string());
         set_config(&get_config_file_path());
@@ -135,29 +183,32 @@ fn cmd_match(matches: &ArgMatches) {
         set_config("");
     }
 
+    // --server 优先于环境变量，仅对本次调用生效
+    let server = match matches.get_one::<String>("server") {
+        Some(s) => Some(s.clone()),
+        None => std::env::var(SERVER_ENV).ok().filter(|s| !s.is_empty()),
+    };
+    let _server_override = match server {
+        Some(s) => Some(override_current_server(&s)?),
+        None => None,
+    };
+
     if matches.get_flag("ui") {
-        let _ = tui_start();
-        return;
+        tui_start().map_err(|e| anyhow!("{}", e))?;
+        return Ok(());
     }
 
     if matches.get_flag("interact") {
         if !INTERACT_STATUS.load(std::sync::atomic::Ordering::SeqCst) {
             interact::run();
-            return;
+            return Ok(());
         }
     }
 
     if let Some(config) = matches.subcommand_matches("config") {
         if let Some(_show) = config.subcommand_matches("show") {
-            let yml = get_current_config_yml();
-            match yml {
-                Ok(str) => {
-                    println!("{}", str);
-                }
-                Err(e) => {
-                    eprintln!("{}", e);
-                }
-            }
+            let yml = get_current_config_yml()?;
+            println!("{}", yml);
         }
 
         if let Some(gen_config) = config.subcommand_matches("gendefault") {
@@ -167,483 +218,642 @@ fn cmd_match(matches: &ArgMatches) {
             } else {
                 file.push_str("config_default.yml")
             }
-            if let Err(e) = generate_default_config(file.as_str()) {
-                log::error!("{}", e);
-                return;
-            };
-            println!("{} created!", file);
+            generate_default_config(file.as_str())?;
+            eprintln!("{} created!", file);
         }
     }
 
     if let Some(server) = matches.subcommand_matches("server") {
-        if let Some(_) = server.subcommand_matches("current") {
-            GLOBAL_RUNTIME.block_on(async move {
-                println!("{:?}", GLOBAL_CURRENT_SERVER.read().unwrap());
-            });
+        if let Some(current) = server.subcommand_matches("current") {
+            let format = output_format(current, OutputFormat::Table)?;
+            let task_server = GLOBAL_CURRENT_SERVER.read().unwrap().clone();
+            let item = ServerItem::new(None, &task_server);
+            let output = format_output(&item, format, |_| {
+                let mut builder = Builder::default();
+                builder.push_record(vec![
+                    item.name.clone(),
+                    item.url.clone(),
+                    item.auth.clone(),
+                    item.tls.clone(),
+                ]);
+                builder.insert_record(0, vec!["name", "url", "auth", "tls"]);
+                builder.build().to_string()
+            })?;
+            println!("{}", output);
         }
 
         if let Some(save) = server.subcommand_matches("add") {
             let name = match save.get_one::<String>("name") {
                 Some(s) => s.clone(),
-                None => {
-                    return;
-                }
+                None => return Err(anyhow!("server name required")),
             };
 
             let url = match save.get_one::<String>("url") {
                 Some(s) => s.clone(),
-                None => {
-                    return;
-                }
+                None => return Err(anyhow!("server url required")),
             };
 
-            let task_server = TaskServer { name, url };
-            match save_task_server_to_cf(&task_server) {
-                Ok(id) => {
-                    println!("server {} saved", id);
+            let mut headers = vec![];
+            if let Some(values) = save.get_many::<String>("header") {
+                for header in values {
+                    headers.push(parse_header(header)?);
                 }
-                Err(e) => log::error!("{:?}", e),
+            }
+            let auth = ServerAuth {
+                bearer_token: save.get_one::<String>("bearer_token").cloned(),
+                basic: save.get_one::<String>("basic_auth").map(|s| parse_basic(s)),
+                headers,
+            };
+            auth.validate()?;
+
+            let tls = ServerTls {
+                ca_cert: save.get_one::<String>("ca_cert").cloned(),
+                client_cert: save.get_one::<String>("client_cert").cloned(),
+                client_key: save.get_one::<String>("client_key").cloned(),
+                insecure: save.get_flag("insecure"),
+            }
+            .canonicalize()?;
+            tls.validate()?;
+
+            let task_server = TaskServer {
+                name,
+                url,
+                auth,
+                tls,
             };
+            let id = save_task_server_to_cf(&task_server)?;
+            println!("{}", id);
+            eprintln!("server {} saved", id);
         }
 
         if let Some(set) = server.subcommand_matches("set") {
-            if let Some(id) = set.get_one::<String>("server_id") {
-                let server_id = id.to_string();
-                println!("{:?}", set_current_server(&server_id));
-                // GLOBAL_RUNTIME.block_on(async move {
-                //     println!("{:?}", set_current_server(&server_id).await);
-                // });
+            if let Some(s) = set.get_one::<String>("server_id") {
+                let url = set_current_server(s)?;
+                eprintln!("current server {} {}", s, url);
             }
         }
 
         if let Some(remove) = server.subcommand_matches("remove") {
-            if let Some(id) = remove.get_one::<String>("server_id") {
-                println!("{:?}", remove_server_from_cf(id));
+            if let Some(s) = remove.get_one::<String>("server_id") {
+                let (id, _) = find_task_server(s)?;
+                remove_server_from_cf(&id)?;
+                eprintln!("server {} removed", id);
             }
         }
 
-        if let Some(_) = server.subcommand_matches("list") {
-            let servers_list = match list_servers_from_cf() {
-                Ok(l) => l,
-                Err(e) => {
-                    log::error!("{}", e);
-                    return;
+        if let Some(list) = server.subcommand_matches("list") {
+            let format = output_format(list, OutputFormat::Table)?;
+            let servers_list = list_servers_from_cf()?
+                .into_iter()
+                .map(|(id, task_server)| ServerItem::new(Some(id), &task_server))
+                .collect::<Vec<ServerItem>>();
+
+            let output = format_output(&servers_list, format, |wide| {
+                let mut builder = Builder::default();
+                for item in servers_list.iter() {
+                    let mut raw = vec![
+                        item.id.clone().unwrap_or_default(),
+                        item.name.clone(),
+                        item.url.clone(),
+                        item.auth.clone(),
+                    ];
+                    if wide {
+                        raw.push(item.tls.clone());
+                    }
+                    builder.push_record(raw);
+                }
+
+                let mut header = vec!["id", "name", "url", "auth"];
+                if wide {
+                    header.push("tls");
                 }
+                builder.insert_record(0, header);
+                builder.build().to_string()
+            })?;
+            println!("{}", output);
+        }
+    }
+
+    if let Some(credential) = matches.subcommand_matches("credential") {
+        if let Some(add) = credential.subcommand_matches("add") {
+            let name = match add.get_one::<String>("name") {
+                Some(s) => s.clone(),
+                None => return Err(anyhow!("profile name required")),
+            };
+            let access_key_id = match add.get_one::<String>("access_key_id") {
+                Some(s) => s.clone(),
+                None => return Err(anyhow!("access key id required")),
+            };
+            let secret_access_key = match add.get_one::<String>("secret_access_key") {
+                Some(s) => s.clone(),
+                None => read_secret("secret access key: ")?,
             };
+            let profile = CredentialProfile {
+                access_key_id,
+                secret_access_key,
+            };
+            save_credential_profile(&name, &profile)?;
+            eprintln!("credential profile {} saved", name);
+        }
 
-            let mut builder = Builder::default();
-            for (id, task_server) in servers_list {
-                let raw = vec![id, task_server.name, task_server.url];
-                builder.push_record(raw);
+        if let Some(remove) = credential.subcommand_matches("remove") {
+            if let Some(name) = remove.get_one::<String>("name") {
+                get_credential_profile(name)?;
+                remove_credential_profile(name)?;
+                eprintln!("credential profile {} removed", name);
             }
+        }
 
-            let header = vec!["id", "name", "url"];
-            builder.insert_record(0, header);
-            let table = builder.build();
-            println!("{}", table);
+        if let Some(list) = credential.subcommand_matches("list") {
+            let format = output_format(list, OutputFormat::Table)?;
+            let profiles = list_credential_profiles()?
+                .into_iter()
+                .map(|(name, profile)| CredentialItem {
+                    name,
+                    access_key_id: mask_access_key_id(&profile.access_key_id),
+                    secret_access_key: mask_secret(&profile.secret_access_key),
+                })
+                .collect::<Vec<CredentialItem>>();
+            let output = format_output(&profiles, format, |_| {
+                let mut builder = Builder::default();
+                for item in profiles.iter() {
+                    builder.push_record([
+                        item.name.clone(),
+                        item.access_key_id.clone(),
+                        item.secret_access_key.clone(),
+                    ]);
+                }
+                builder.insert_record(0, ["name", "access key id", "secret access key"]);
+                builder.build().to_string()
+            })?;
+            println!("{}", output);
         }
     }
 
     if let Some(task) = matches.subcommand_matches("task") {
         if let Some(show) = task.subcommand_matches("show") {
             if let Some(id) = show.get_one::<String>("taskid") {
-                let task_id = id.to_string();
-                GLOBAL_RUNTIME.block_on(async move {
-                    let id = TaskId { task_id };
-                    let task = match task_show(&id).await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    let task = match task.data {
-                        Some(t) => t,
-                        None => {
-                            return;
-                        }
-                    };
-                    let task_json = match struct_to_json_string_prettry(&task) {
-                        Ok(j) => j,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    println!("{}", task_json);
-                });
+                let id = TaskId {
+                    task_id: id.to_string(),
+                };
+                let format = output_format(show, OutputFormat::Json)?;
+                let mut task = GLOBAL_RUNTIME.block_on(async move { task_show(&id).await })?;
+                if !show.get_flag("show_secrets") {
+                    task = mask_task_secrets(&task);
+                }
+                println!(
+                    "{}",
+                    format_output(&task, format, |wide| task_table(&task, wide))?
+                );
             }
         }
 
         if let Some(create) = task.subcommand_matches("create") {
-            if let Some(json) = create.get_one::<String>("taskjson") {
-                let task_json = json.to_string();
-                GLOBAL_RUNTIME.block_on(async move {
-                    let task = match json_to_struct::<Task>(&task_json) {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{}", e);
-                            return;
-                        }
-                    };
+            let tasks = match create.get_one::<String>("file") {
+                Some(f) => read_tasks(f)?,
+                None => match create.get_one::<String>("taskjson") {
+                    Some(json) => vec![json_to_struct::<Task>(json)?],
+                    None => return Err(anyhow!("task json or file required")),
+                },
+            };
 
-                    let task = match task_create(&task).await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
+            let assume_yes = create.get_flag("yes");
+            if tasks.len().eq(&1) {
+                let task = &tasks[0];
+                if !confirm_truncate(task, assume_yes) {
+                    return Err(CliError::Canceled.into());
+                }
+                let task = resolve_task_secrets(task)?;
+                let t = GLOBAL_RUNTIME.block_on(async { task_create(&task).await })?;
+                println!("{}", t.task_id);
+                eprintln!("task {} created", t.task_id);
+                return Ok(());
+            }
 
-                    let task = match task.data {
-                        Some(t) => t,
-                        None => {
-                            return;
-                        }
-                    };
-                    println!("task {} created", task.task_id.as_str());
+            // 多任务创建时逐个提交，失败的任务汇总到结果表格
+            let format = output_format(create, OutputFormat::Table)?;
+            let mut results = vec![];
+            for task in tasks {
+                let created = match confirm_truncate(&task, assume_yes) {
+                    true => resolve_task_secrets(&task).and_then(|t| {
+                        GLOBAL_RUNTIME
+                            .block_on(async { task_create(&t).await })
+                            .map_err(anyhow::Error::from)
+                    }),
+                    false => Err(CliError::Canceled.into()),
+                };
+                results.push(match created {
+                    Ok(t) => BulkResult {
+                        task_id: t.task_id,
+                        ok: true,
+                        error: None,
+                    },
+                    // 任务未创建时以任务描述中的 id 标识，并在错误中附带任务名称
+                    Err(e) => BulkResult {
+                        task_id: task.task_id(),
+                        ok: false,
+                        error: Some(format!("{}: {}", task.task_name(), e)),
+                    },
                 });
             }
+            print_bulk_results(&results, format)?;
         }
 
         if let Some(update) = task.subcommand_matches("update") {
-            let task_id = match update.get_one::<String>("taskid") {
-                Some(s) => s.clone(),
-                None => {
-                    return;
-                }
-            };
-
-            let task_json = match update.get_one::<String>("taskjson") {
-                Some(s) => s.clone(),
-                None => {
-                    return;
-                }
-            };
+            update_task(update)?;
+        }
 
-            let task = match json_to_struct::<Task>(&task_json) {
-                Ok(t) => t,
-                Err(e) => {
-                    log::error!("{:?}", e);
-                    return;
-                }
+        if let Some(clone) = task.subcommand_matches("clone") {
+            let id = match clone.get_one::<String>("taskid") {
+                Some(id) => TaskId {
+                    task_id: id.clone(),
+                },
+                None => return Err(anyhow!("task id required")),
             };
-
-            let req_update = ReqTaskUpdate { task_id, task };
-
-            GLOBAL_RUNTIME.block_on(async move {
-                let resp = match task_update(&req_update).await {
-                    Ok(t) => t,
-                    Err(e) => {
-                        log::error!("{:?}", e);
-                        return;
-                    }
-                };
-
-                match resp.code.eq(&0) {
-                    true => println!("update task {} ok", req_update.task_id.as_str()),
-                    false => eprintln!("{:?}", resp),
-                };
-            });
+            let overrides = clone
+                .get_many::<String>("set")
+                .map(|sets| sets.map(|s| parse_path_assignment(s)).collect())
+                .unwrap_or(Ok(vec![]))?;
+
+            let client = ApiClient::current()?;
+            let source = GLOBAL_RUNTIME.block_on(async { client.task_show(&id).await })?;
+            let cloned = clone_task(
+                &source,
+                clone.get_one::<String>("name").map(|n| n.as_str()),
+                &overrides,
+            )?;
+            if !confirm_truncate(&cloned, clone.get_flag("yes")) {
+                return Err(CliError::Canceled.into());
+            }
+            let cloned = resolve_task_secrets(&cloned)?;
+            let new_id = GLOBAL_RUNTIME.block_on(async { client.task_create(&cloned).await })?;
+            println!("{}", new_id.task_id);
+            eprintln!("task {} cloned from {}", new_id.task_id, id.task_id);
         }
 
-        if let Some(show) = task.subcommand_matches("start") {
-            if let Some(id) = show.get_one::<String>("taskid") {
-                let task_id = id.to_string();
-                GLOBAL_RUNTIME.block_on(async move {
-                    let req_id = TaskId { task_id };
-                    let resp = match task_start(&req_id).await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    match resp.code.eq(&0) {
-                        true => println!("start {} ok", id),
-                        false => println!("{:?}", resp),
-                    }
-                });
+        for op in [BulkOp::Start, BulkOp::Stop, BulkOp::Clean, BulkOp::Remove] {
+            if let Some(args) = task.subcommand_matches(op.name()) {
+                run_task_op(op, args)?;
             }
         }
 
-        if let Some(show) = task.subcommand_matches("stop") {
-            if let Some(id) = show.get_one::<String>("taskid") {
-                let task_id = id.to_string();
-                GLOBAL_RUNTIME.block_on(async move {
-                    let req_id = TaskId { task_id };
-                    let resp = match task_stop(&req_id).await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
+        if let Some(checkpoint) = task.subcommand_matches("checkpoint") {
+            if let Some(id) = checkpoint.get_one::<String>("taskid") {
+                let id = TaskId {
+                    task_id: id.to_string(),
+                };
+                let checkpoint_info =
+                    GLOBAL_RUNTIME.block_on(async move { task_checkpoint(&id).await })?;
+
+                let format = output_format(checkpoint, OutputFormat::Table)?;
+                if format.is_structured() {
+                    println!(
+                        "{}",
+                        format_output(&checkpoint_info, format, |_| "".to_string())?
+                    );
+                    return Ok(());
+                }
 
-                    match resp.code.eq(&0) {
-                        true => println!("start {} ok", id),
-                        false => println!("{:?}", resp),
-                    }
-                });
+                let mut builder = Builder::default();
+                builder.push_record(vec!["task id".to_string(), checkpoint_info.task_id.clone()]);
+                builder.push_record(vec![
+                    "task stage".to_string(),
+                    checkpoint_info.task_stage.to_string(),
+                ]);
+                builder.push_record(vec![
+                    "executed file".to_string(),
+                    checkpoint_info.executed_file.path.clone(),
+                ]);
+                builder.push_record(vec![
+                    "executed file size".to_string(),
+                    checkpoint_info.executed_file.size.to_string(),
+                ]);
+                builder.push_record(vec![
+                    "executed offset".to_string(),
+                    checkpoint_info.executed_file_position.offset.to_string(),
+                ]);
+                builder.push_record(vec![
+                    "executed lines".to_string(),
+                    format!(
+                        "{}/{} ({:.2}%)",
+                        checkpoint_info.executed_file_position.line_num,
+                        checkpoint_info.executed_file.total_lines,
+                        checkpoint_info.executed_percent()
+                    ),
+                ]);
+                builder.push_record(vec![
+                    "file for notify".to_string(),
+                    checkpoint_info.file_for_notify.clone().unwrap_or_default(),
+                ]);
+                builder.push_record(vec![
+                    "task begin time".to_string(),
+                    timestamp_to_string(checkpoint_info.task_begin_timestamp),
+                ]);
+                builder.push_record(vec![
+                    "checkpoint modify time".to_string(),
+                    timestamp_to_string(checkpoint_info.modify_checkpoint_timestamp),
+                ]);
+                builder.insert_record(0, vec!["item", "value"]);
+                let table = builder.build();
+                println!("{}", table);
             }
         }
 
-        if let Some(remove) = task.subcommand_matches("clean") {
-            if let Some(id) = remove.get_one::<String>("taskid") {
-                let task_id = id.to_string();
-                GLOBAL_RUNTIME.block_on(async move {
-                    let _ = match task_clean(&TaskId {
-                        task_id: task_id.clone(),
+        if let Some(list_all) = task.subcommand_matches("list_all") {
+            let format = output_format(list_all, OutputFormat::Table)?;
+            let items = GLOBAL_RUNTIME.block_on(async move {
+                let tasks = list_all_tasks().await?;
+
+                let mut items = vec![];
+                for task in tasks {
+                    let status = match task_status(&TaskId {
+                        task_id: task.task.task_id(),
                     })
                     .await
                     {
-                        Ok(_) => {}
+                        Ok(Some(s)) => s.status.to_string(),
+                        Ok(None) => "stopped".to_string(),
                         Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
+                            log::error!("{}", e);
+                            "stopped".to_string()
                         }
                     };
+                    items.push(TaskListItem {
+                        task_id: task.task.task_id(),
+                        name: task.task.task_name(),
+                        task_type: task.task.task_type().to_string(),
+                        status,
+                        source: task.task.task_source().to_string(),
+                        target: task.task.task_target().to_string(),
+                    });
+                }
+                Ok::<Vec<TaskListItem>, ClientError>(items)
+            })?;
 
-                    println!("task {} removed", task_id);
-                });
-            }
+            let output = format_output(&items, format, |wide| {
+                let mut builder = Builder::default();
+                for item in items.iter() {
+                    let mut raw = vec![
+                        item.task_id.clone(),
+                        item.name.clone(),
+                        item.task_type.clone(),
+                        item.status.clone(),
+                    ];
+                    if wide {
+                        raw.push(item.source.clone());
+                        raw.push(item.target.clone());
+                    }
+                    builder.push_record(raw);
+                }
+
+                let mut header = vec!["id", "name", "task type", "status"];
+                if wide {
+                    header.extend(["source", "target"]);
+                }
+                builder.insert_record(0, header);
+                builder.build().to_string()
+            })?;
+            println!("{}", output);
         }
 
-        if let Some(remove) = task.subcommand_matches("remove") {
-            if let Some(id) = remove.get_one::<String>("taskid") {
-                let task_id = id.to_string();
-                GLOBAL_RUNTIME.block_on(async move {
-                    let _ = match task_remove(&TaskId {
-                        task_id: task_id.clone(),
-                    })
-                    .await
-                    {
-                        Ok(_) => {}
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
+        if let Some(export) = task.subcommand_matches("export") {
+            export_tasks(export)?;
+        }
 
-                    println!("task {} removed", task_id);
-                });
-            }
+        if let Some(import) = task.subcommand_matches("import") {
+            import_tasks(import)?;
         }
 
-        if let Some(_) = task.subcommand_matches("list_all") {
-            GLOBAL_RUNTIME.block_on(async move {
-                let reps = match list_all_tasks().await {
-                    Ok(r) => r,
-                    Err(e) => {
-                        log::error!("{:?}", e);
-                        return;
-                    }
-                };
-                let tasks = match reps.data {
-                    Some(v) => v,
-                    None => return,
+        if let Some(all_living) = task.subcommand_matches("all_living") {
+            let format = output_format(all_living, OutputFormat::Table)?;
+            let items = GLOBAL_RUNTIME.block_on(async move {
+                let status_list = task_all_living().await?;
+                // 任务名称及源、目标从任务列表一次获取
+                let tasks = match status_list.is_empty() {
+                    true => HashMap::new(),
+                    false => list_all_tasks()
+                        .await?
+                        .into_iter()
+                        .map(|t| (t.task.task_id(), t.task))
+                        .collect::<HashMap<String, Task>>(),
                 };
 
+                let mut items = vec![];
+                for status in status_list.iter() {
+                    let (name, source, target) = match tasks.get(&status.task_id) {
+                        Some(t) => (
+                            t.task_name(),
+                            t.task_source().to_string(),
+                            t.task_target().to_string(),
+                        ),
+                        None => ("".to_string(), "".to_string(), "".to_string()),
+                    };
+                    items.push(LivingTaskItem {
+                        task_id: status.task_id.clone(),
+                        name,
+                        task_type: status.status_type().to_string(),
+                        stage: status.status.to_string(),
+                        start_time: status.start_time,
+                        source,
+                        target,
+                    });
+                }
+                Ok::<Vec<LivingTaskItem>, ClientError>(items)
+            })?;
+
+            let output = format_output(&items, format, |wide| {
                 let mut builder = Builder::default();
-                for task in tasks {
+                for item in items.iter() {
                     let mut raw = vec![
-                        task.task.task_id(),
-                        task.task.task_name(),
-                        task.task.task_type().to_string(),
+                        item.task_id.clone(),
+                        item.name.clone(),
+                        item.task_type.clone(),
+                        item.stage.clone(),
+                        timestamp_to_string(i128::from(item.start_time)),
                     ];
-                    let status = match task_status(&TaskId {
-                        task_id: task.task.task_id(),
-                    })
-                    .await
-                    {
-                        Ok(t_s) => match t_s.data {
-                            Some(s) => s.status.to_string(),
-                            None => "stopped".to_string(),
-                        },
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            "stopped".to_string()
-                        }
-                    };
-                    raw.push(status);
+                    if wide {
+                        raw.push(item.source.clone());
+                        raw.push(item.target.clone());
+                    }
                     builder.push_record(raw);
                 }
 
-                let header = vec!["id", "name", "task type", "status"];
+                let mut header = vec!["id", "name", "task type", "stage", "start time"];
+                if wide {
+                    header.extend(["source", "target"]);
+                }
                 builder.insert_record(0, header);
-                let table = builder.build();
-                println!("{}", table);
-            });
+                builder.build().to_string()
+            })?;
+            println!("{}", output);
+        }
+
+        if let Some(preview) = task.subcommand_matches("preview") {
+            if let Some(file) = preview.get_one::<String>("task_file") {
+                let task = read_task(file)?;
+
+                let mut writer: Box<dyn Write> = match preview.get_one::<String>("out_file") {
+                    Some(out) => Box::new(BufWriter::new(File::create(out)?)),
+                    None => Box::new(BufWriter::new(io::stdout())),
+                };
+
+                let (files, total_bytes) = preview_local_task(&task, &mut writer)?;
+                eprintln!(
+                    "files: {}, total bytes: {} ({})",
+                    files,
+                    total_bytes,
+                    byte_size_usize_to_str(usize::try_from(total_bytes).unwrap_or(usize::MAX))
+                );
+            }
         }
 
-        if let Some(start) = task.subcommand_matches("start") {
-            if let Some(id) = start.get_one::<String>("taskid") {
-                println!("task id:{}", id);
+        if let Some(validate) = task.subcommand_matches("validate") {
+            if let Some(file) = validate.get_one::<String>("task_file") {
+                let tasks = read_tasks(file)?;
+
+                let mut violations_count = 0;
+                for (idx, task) in tasks.iter().enumerate() {
+                    let violations = validate_task(task);
+                    violations_count += violations.len();
+                    for v in violations {
+                        match tasks.len() {
+                            1 => println!("{}", v),
+                            _ => println!("[{}] {}", idx, v),
+                        }
+                    }
+                }
+
+                if violations_count > 0 {
+                    return Err(CliError::ValidationFailed(violations_count).into());
+                }
+                eprintln!("{} ok", file);
             }
         }
 
         if let Some(analyze) = task.subcommand_matches("analyze") {
+            if let Some(file) = analyze.get_one::<String>("local") {
+                let format = output_format(analyze, OutputFormat::Table)?;
+                let task = read_task(file)?;
+                let analysis = analyze_local_task(&task)?;
+
+                let output = format_output(&analysis, format, |wide| {
+                    let mut builder = Builder::default();
+                    builder.push_record(vec!["files".to_string(), analysis.files.to_string()]);
+                    builder.push_record(vec![
+                        "total bytes".to_string(),
+                        format!(
+                            "{} ({})",
+                            analysis.total_bytes,
+                            byte_size_usize_to_str(
+                                usize::try_from(analysis.total_bytes).unwrap_or(usize::MAX)
+                            )
+                        ),
+                    ]);
+                    format!(
+                        "{}\n{}",
+                        builder.build(),
+                        size_distributed_table(
+                            &analysis.size_distributed,
+                            wide || analyze.get_flag("chart")
+                        )
+                    )
+                })?;
+                println!("{}", output);
+            }
+
             if let Some(id) = analyze.get_one::<String>("taskid") {
-                println!("task id:{}", id);
-                test_reqwest()
+                let id = TaskId {
+                    task_id: id.to_string(),
+                };
+                let format = output_format(analyze, OutputFormat::Table)?;
+                let distributed =
+                    GLOBAL_RUNTIME.block_on(async move { task_analyze(&id).await })?;
+                let output = format_output(&distributed, format, |wide| {
+                    size_distributed_table(&distributed, wide || analyze.get_flag("chart"))
+                })?;
+                println!("{}", output);
             }
         }
 
-        if let Some(show) = task.subcommand_matches("status") {
-            if let Some(id) = show.get_one::<String>("taskid") {
-                let task_id = id.to_string();
-                GLOBAL_RUNTIME.block_on(async move {
-                    let id = TaskId { task_id };
-                    let task = match task_status(&id).await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    let task = match task.data {
-                        Some(t) => t,
-                        None => {
-                            return;
-                        }
-                    };
-                    let task_json = match struct_to_json_string_prettry(&task) {
-                        Ok(j) => j,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
+        if let Some(status) = task.subcommand_matches("status") {
+            if let Some(id) = status.get_one::<String>("taskid") {
+                let req_id = TaskId {
+                    task_id: id.to_string(),
+                };
+                let format = output_format(status, OutputFormat::Json)?;
+                match GLOBAL_RUNTIME.block_on(async move { task_status(&req_id).await })? {
+                    Some(s) => println!(
+                        "{}",
+                        format_output(&s, format, |wide| task_status_table(&s, wide))?
+                    ),
+                    None => eprintln!("task {} is not running", id),
+                };
+            }
+        }
 
-                    println!("{}", task_json);
-                });
+        if let Some(watch) = task.subcommand_matches("watch") {
+            if let Some(id) = watch.get_one::<String>("taskid") {
+                let req_id = TaskId {
+                    task_id: id.to_string(),
+                };
+                let interval = match watch.get_one::<u64>("interval") {
+                    Some(i) => Duration::from_secs(*i),
+                    None => Duration::from_secs(2),
+                };
+                GLOBAL_RUNTIME.block_on(watch_task(&req_id, interval))?;
             }
         }
     }
 
     if let Some(template) = matches.subcommand_matches("template") {
         if let Some(transfer) = template.subcommand_matches("transfer") {
-            if let Some(_) = transfer.subcommand_matches("oss2oss") {
-                GLOBAL_RUNTIME.block_on(async move {
-                    let task = match template_transfer_oss2oss().await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    let task = match task.data {
-                        Some(t) => t,
-                        None => {
-                            return;
-                        }
-                    };
-                    let task_json = match struct_to_json_string_prettry(&task) {
-                        Ok(j) => j,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    println!("{}", task_json);
-                });
+            if let Some(oss2oss) = transfer.subcommand_matches("oss2oss") {
+                let task = match oss2oss.get_flag("offline") {
+                    true => OfflineTemplate::TransferOss2Oss.task(),
+                    false => GLOBAL_RUNTIME.block_on(template_transfer_oss2oss())?,
+                };
+                output_template(&task, oss2oss)?;
             }
-            if let Some(_) = transfer.subcommand_matches("oss2local") {
-                GLOBAL_RUNTIME.block_on(async move {
-                    let task = match template_transfer_oss2local().await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
 
-                    let task = match task.data {
-                        Some(t) => t,
-                        None => {
-                            return;
-                        }
-                    };
-                    let task_json = match struct_to_json_string_prettry(&task) {
-                        Ok(j) => j,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    println!("{}", task_json);
-                });
+            if let Some(oss2local) = transfer.subcommand_matches("oss2local") {
+                let task = match oss2local.get_flag("offline") {
+                    true => OfflineTemplate::TransferOss2Local.task(),
+                    false => GLOBAL_RUNTIME.block_on(template_transfer_oss2local())?,
+                };
+                output_template(&task, oss2local)?;
             }
-            if let Some(_) = transfer.subcommand_matches("local2oss") {
-                GLOBAL_RUNTIME.block_on(async move {
-                    let task = match template_transfer_local2oss().await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    let task = match task.data {
-                        Some(t) => t,
-                        None => {
-                            return;
-                        }
-                    };
-                    let task_json = match struct_to_json_string_prettry(&task) {
-                        Ok(j) => j,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
 
-                    println!("{}", task_json);
-                });
+            if let Some(local2oss) = transfer.subcommand_matches("local2oss") {
+                let task = match local2oss.get_flag("offline") {
+                    true => OfflineTemplate::TransferLocal2Oss.task(),
+                    false => GLOBAL_RUNTIME.block_on(template_transfer_local2oss())?,
+                };
+                output_template(&task, local2oss)?;
             }
 
-            if let Some(_) = transfer.subcommand_matches("local2local") {
-                GLOBAL_RUNTIME.block_on(async move {
-                    let task = match template_transfer_local2local().await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    let task = match task.data {
-                        Some(t) => t,
-                        None => {
-                            return;
-                        }
-                    };
-                    let task_json = match struct_to_json_string_prettry(&task) {
-                        Ok(j) => j,
-                        Err(e) => {
-                            log::error!("{:?}", e);
-                            return;
-                        }
-                    };
-
-                    println!("{}", task_json);
-                });
+            if let Some(local2local) = transfer.subcommand_matches("local2local") {
+                let task = match local2local.get_flag("offline") {
+                    true => OfflineTemplate::TransferLocal2Local.task(),
+                    false => GLOBAL_RUNTIME.block_on(template_transfer_local2local())?,
+                };
+                output_template(&task, local2local)?;
             }
         }
 
         if let Some(truncate_bucket) = template.subcommand_matches("truncate_bucket") {
-            println!("template truncate");
+            let task = match truncate_bucket.get_flag("offline") {
+                true => OfflineTemplate::TruncateBucket.task(),
+                false => GLOBAL_RUNTIME.block_on(template_truncate_bucket())?,
+            };
+            output_template(&task, truncate_bucket)?;
         }
 
         if let Some(compare) = template.subcommand_matches("compare") {
-            println!("template compare");
+            let task = match compare.get_flag("offline") {
+                true => OfflineTemplate::Compare.task(),
+                false => GLOBAL_RUNTIME.block_on(template_compare())?,
+            };
+            output_template(&task, compare)?;
         }
     }
 
@@ -659,121 +869,669 @@ fn cmd_match(matches: &ArgMatches) {
 
     if let Some(gen_file) = matches.subcommand_matches("gen_file") {
         let file_size = match gen_file.get_one::<String>("file_size") {
-            Some(s) => {
-                let size = byte_size_str_to_usize(s);
-                match size {
-                    Ok(s) => s,
-                    Err(e) => {
-                        log::error!("{}", e);
-                        return;
-                    }
-                }
-            }
-            None => {
-                return;
-            }
+            Some(s) => byte_size_str_to_usize(s)?,
+            None => return Err(anyhow!("file size required")),
         };
         let chunk: usize = match gen_file.get_one::<String>("chunk_size") {
-            Some(s) => {
-                let size = byte_size_str_to_usize(s);
-                match size {
-                    Ok(s) => s,
-                    Err(e) => {
-                        log::error!("{}", e);
-                        return;
-                    }
-                }
-            }
-            None => {
-                return;
-            }
+            Some(s) => byte_size_str_to_usize(s)?,
+            None => return Err(anyhow!("chunk size required")),
         };
 
         let file = match gen_file.get_one::<String>("file_name") {
             Some(s) => s,
-            None => {
-                return;
-            }
+            None => return Err(anyhow!("file name required")),
         };
 
-        if let Err(e) = generate_file(file_size, chunk, file) {
-            log::error!("{}", e);
-        };
+        generate_file(file_size, chunk, file)?;
     }
 
     if let Some(gen_file) = matches.subcommand_matches("gen_files") {
         let dir = match gen_file.get_one::<String>("dir") {
             Some(s) => s,
-            None => {
-                return;
-            }
+            None => return Err(anyhow!("dir required")),
         };
         let file_prefix_len: usize = match gen_file.get_one("file_prefix_len") {
             Some(s) => *s,
-            None => {
-                return;
-            }
+            None => return Err(anyhow!("file prefix length required")),
         };
 
         let file_size = match gen_file.get_one::<String>("file_size") {
-            Some(s) => {
-                let size = byte_size_str_to_usize(s);
-                match size {
-                    Ok(s) => s,
-                    Err(e) => {
-                        log::error!("{}", e);
-                        return;
-                    }
-                }
-            }
-            None => {
-                return;
-            }
+            Some(s) => byte_size_str_to_usize(s)?,
+            None => return Err(anyhow!("file size required")),
         };
 
         let chunk_size: usize = match gen_file.get_one::<String>("chunk_size") {
-            Some(s) => {
-                let size = byte_size_str_to_usize(s);
-                match size {
-                    Ok(s) => s,
-                    Err(e) => {
-                        log::error!("{}", e);
-                        return;
-                    }
-                }
-            }
-            None => {
-                return;
-            }
+            Some(s) => byte_size_str_to_usize(s)?,
+            None => return Err(anyhow!("chunk size required")),
         };
 
         let file_quantity: usize = match gen_file.get_one("file_quantity") {
             Some(s) => *s,
-            None => {
-                return;
-            }
+            None => return Err(anyhow!("file quantity required")),
         };
 
-        if let Err(e) = generate_files(
+        generate_files(
             dir.as_str(),
             file_prefix_len,
             file_size,
             chunk_size,
             file_quantity,
-        ) {
-            log::error!("{}", e);
-        };
+        )?;
     }
 
     if let Some(_) = matches.subcommand_matches("tree") {
         let tree = get_cmd_tree(&CLIAPP);
         println!("{}", tree);
     }
+
+    Ok(())
+}
+
+// 读取全局输出格式，未指定时使用命令默认格式
+fn output_format(args: &ArgMatches, default: OutputFormat) -> Result<OutputFormat> {
+    match args.get_one::<String>("output") {
+        Some(f) => OutputFormat::parse(f),
+        None => Ok(default),
+    }
+}
+
+#[derive(Serialize)]
+struct ServerItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    name: String,
+    url: String,
+    // 仅输出认证方式概要，不输出凭据
+    auth: String,
+    tls: String,
+}
+
+impl ServerItem {
+    fn new(id: Option<String>, task_server: &TaskServer) -> Self {
+        Self {
+            id,
+            name: task_server.name.clone(),
+            url: task_server.url.clone(),
+            auth: task_server.auth.summary(),
+            tls: task_server.tls.summary(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CredentialItem {
+    name: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+#[derive(Serialize)]
+struct ImportResultItem {
+    source_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    task_id: Option<String>,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TaskListItem {
+    task_id: String,
+    name: String,
+    task_type: String,
+    status: String,
+    source: String,
+    target: String,
+}
+
+#[derive(Serialize)]
+struct LivingTaskItem {
+    task_id: String,
+    name: String,
+    task_type: String,
+    stage: String,
+    start_time: u64,
+    source: String,
+    target: String,
+}
+
+// 任务描述表格，wide 模式附加任务属性
+fn task_table(task: &Task, wide: bool) -> String {
+    let mut builder = Builder::default();
+    builder.push_record(vec!["task id".to_string(), task.task_id()]);
+    builder.push_record(vec!["name".to_string(), task.task_name()]);
+    builder.push_record(vec!["task type".to_string(), task.task_type().to_string()]);
+    builder.push_record(vec!["source".to_string(), task.task_source().to_string()]);
+    builder.push_record(vec!["target".to_string(), task.task_target().to_string()]);
+    builder.push_record(vec!["meta dir".to_string(), task.meta_dir()]);
+    if wide {
+        let attributes = match task {
+            Task::Transfer(t) => struct_to_yaml_string(&t.attributes),
+            Task::Compare(c) => struct_to_yaml_string(&c.attributes),
+            Task::TruncateBucket(t) => struct_to_yaml_string(&t.attributes),
+        };
+        builder.push_record(vec![
+            "attributes".to_string(),
+            attributes.unwrap_or_default(),
+        ]);
+    }
+    builder.insert_record(0, vec!["item", "value"]);
+    builder.build().to_string()
+}
+
+// 任务状态表格，wide 模式附加原始时间戳
+fn task_status_table(status: &TaskStatus, wide: bool) -> String {
+    let mut builder = Builder::default();
+    builder.push_record(vec!["task id".to_string(), status.task_id.clone()]);
+    builder.push_record(vec![
+        "task type".to_string(),
+        status.status_type().to_string(),
+    ]);
+    builder.push_record(vec!["status".to_string(), status.status.to_string()]);
+    builder.push_record(vec![
+        "start time".to_string(),
+        timestamp_to_string(i128::from(status.start_time)),
+    ]);
+    if wide {
+        builder.push_record(vec![
+            "start timestamp".to_string(),
+            status.start_time.to_string(),
+        ]);
+    }
+    builder.insert_record(0, vec!["item", "value"]);
+    builder.build().to_string()
+}
+
+// 轮询任务状态并以进度条展示，存量阶段按 checkpoint 展示执行进度，
+// 任务因错误停止时返回 TaskBroken
+async fn watch_task(id: &TaskId, interval: Duration) -> Result<()> {
+    let client = ApiClient::current()?;
+    let pb = ProgressBar::new(100);
+    pb.set_style(
+        ProgressStyle::with_template("{spinner} [{bar:40}] {pos:>3}% {msg}")?.progress_chars("#>-"),
+    );
+    pb.enable_steady_tick(Duration::from_millis(200));
+
+    let mut prev_stage: Option<String> = None;
+    let watched = client
+        .task_watch(id, interval, |status, percent| {
+            let stage = status.status.to_string();
+            if let Some(prev) = &prev_stage {
+                if !prev.eq(&stage) {
+                    // 非终端环境进度条隐藏，阶段变化直接输出到 stderr
+                    let msg = format!("stage {} -> {}", prev, stage);
+                    match pb.is_hidden() {
+                        true => eprintln!("{}", msg),
+                        false => pb.println(msg),
+                    }
+                }
+            }
+            if let Some(percent) = percent {
+                pb.set_position(percent as u64);
+            }
+
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            pb.set_message(format!(
+                "{} elapsed {}",
+                stage,
+                FormattedDuration(Duration::from_secs(now.saturating_sub(status.start_time)))
+            ));
+            prev_stage = Some(stage);
+        })
+        .await;
+    let last = match watched {
+        Ok(last) => last,
+        Err(e) => {
+            pb.abandon();
+            return Err(e.into());
+        }
+    };
+    pb.finish_and_clear();
+
+    let last = match last {
+        Some(s) => s,
+        None => return Err(anyhow!("task {} is not running", id.task_id)),
+    };
+    match client.task_stop_reason(id, &last).await? {
+        Some(TaskStopReason::Finish) => {
+            eprintln!("task {} finished", id.task_id);
+            Ok(())
+        }
+        Some(TaskStopReason::Broken) => Err(CliError::TaskBroken(id.task_id.clone()).into()),
+        None => Err(CliError::TaskStopUnknown(id.task_id.clone()).into()),
+    }
+}
+
+// 按指定格式输出模板，指定 file 时写入文件
+fn output_template(template: &Task, args: &ArgMatches) -> Result<()> {
+    // 未显式指定 --format 时沿用全局 --output 的 json 或 yaml
+    let format = match args.value_source("format") {
+        Some(ValueSource::DefaultValue) | None => match args.get_one::<String>("output") {
+            Some(o) if o.eq("yaml") || o.eq("json") => Some(o),
+            _ => args.get_one::<String>("format"),
+        },
+        _ => args.get_one::<String>("format"),
+    };
+    let content = match format.map(|f| f.as_str()) {
+        Some("yaml") => struct_to_yaml_string(template).map(|y| task_yaml_with_comments(&y))?,
+        _ => struct_to_json_string_prettry(template)?,
+    };
+
+    match args.get_one::<String>("file") {
+        Some(file) => {
+            fs::write(file, content)?;
+            eprintln!("{} created!", file);
+        }
+        None => println!("{}", content),
+    }
+    Ok(())
+}
+
+// 从文件或标准输入读取任务描述，自动识别 json 与 yaml，yaml 支持多文档
+fn read_tasks(path: &str) -> Result<Vec<Task>> {
+    let content = read_file_or_stdin(path)?;
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') {
+        return Ok(vec![json_to_struct::<Task>(trimmed)?]);
+    }
+    if trimmed.starts_with('[') {
+        return json_to_struct::<Vec<Task>>(trimmed);
+    }
+    yaml_documents_to_structs::<Task>(&content)
+}
+
+// 读取单个任务描述
+fn read_task(path: &str) -> Result<Task> {
+    let mut tasks = read_tasks(path)?;
+    match tasks.len() {
+        1 => Ok(tasks.remove(0)),
+        0 => Err(anyhow!("no task found in {}", path)),
+        _ => Err(anyhow!("{} contains more than one task", path)),
+    }
+}
+
+// 清空 bucket 任务具有破坏性，提交前需用户确认，其他任务直接通过
+fn confirm_truncate(task: &Task, assume_yes: bool) -> bool {
+    let truncate = match task {
+        Task::TruncateBucket(t) => t,
+        _ => return true,
+    };
+    if assume_yes {
+        return true;
+    }
+
+    confirm(&format!(
+        "task {} will delete objects in bucket {} with prefix '{}', continue? [y/N] ",
+        truncate.name,
+        truncate.target.bucket,
+        truncate.target.prefix.clone().unwrap_or_default()
+    ))
+}
+
+// 从 stdin 读取一行凭据，避免凭据出现在命令行历史中
+fn read_secret(prompt: &str) -> Result<String> {
+    if io::stdin().is_terminal() {
+        eprint!("{}", prompt);
+        let _ = io::stderr().flush();
+    }
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let secret = input.trim_end_matches(['\r', '\n']).to_string();
+    if secret.is_empty() {
+        return Err(anyhow!("empty secret"));
+    }
+    Ok(secret)
+}
+
+// 在 stderr 输出提示并读取用户确认，无输入时视为取消
+fn confirm(prompt: &str) -> bool {
+    eprint!("{}", prompt);
+    let _ = io::stderr().flush();
+    let mut input = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(0) => {
+            eprintln!();
+            eprintln!("no confirmation input, use --yes to skip confirmation");
+            false
+        }
+        Ok(_) => matches!(input.trim().to_lowercase().as_str(), "y" | "yes"),
+        Err(_) => false,
+    }
+}
+
+// 以完整任务描述替换或以 merge patch 及属性覆盖修改任务，确认变更后提交
+fn update_task(args: &ArgMatches) -> Result<()> {
+    let id = match args.get_one::<String>("taskid") {
+        Some(s) => TaskId { task_id: s.clone() },
+        None => return Err(anyhow!("task id required")),
+    };
+    let client = ApiClient::current()?;
+    let current = GLOBAL_RUNTIME.block_on(async { client.task_show(&id).await })?;
+
+    let task = match (
+        args.get_one::<String>("file"),
+        args.get_one::<String>("taskjson"),
+    ) {
+        (Some(f), _) => read_task(f)?,
+        (None, Some(json)) => json_to_struct::<Task>(json)?,
+        (None, None) => {
+            let patch = match args.get_one::<String>("patch") {
+                Some(p) => Some(serde_yaml::from_str::<serde_json::Value>(
+                    &read_file_or_stdin(p)?,
+                )?),
+                None => None,
+            };
+            let sets = args
+                .get_many::<String>("set")
+                .map(|sets| sets.map(|s| parse_path_assignment(s)).collect())
+                .unwrap_or(Ok(vec![]))?;
+            patch_task(&current, patch.as_ref(), &sets)?
+        }
+    };
+    let task = resolve_task_secrets(&task)?;
+
+    let changed = |diff: &Vec<DiffLine>| diff.iter().any(|l| !matches!(l, DiffLine::Same(_)));
+    if !changed(&task_diff(&current, &task)?) {
+        eprintln!("task {} unchanged", id.task_id);
+        return Ok(());
+    }
+    // 展示时隐藏凭据，仅凭据变更时单独提示
+    let diff = task_diff(&mask_task_secrets(&current), &mask_task_secrets(&task))?;
+    match changed(&diff) {
+        true => eprintln!("{}", format_diff(&diff, 3, io::stderr().is_terminal())),
+        false => eprintln!("credentials changed"),
+    }
+    if !args.get_flag("yes") && !confirm(&format!("update task {}? [y/N] ", id.task_id)) {
+        return Err(CliError::Canceled.into());
+    }
+
+    let req_update = ReqTaskUpdate {
+        task_id: id.task_id,
+        task,
+    };
+    GLOBAL_RUNTIME.block_on(async { client.task_update(&req_update).await })?;
+    eprintln!("update task {} ok", req_update.task_id);
+    Ok(())
+}
+
+// 读取 task_selector_args 定义的任务选择参数
+fn task_selector(args: &ArgMatches) -> Result<TaskSelector> {
+    Ok(TaskSelector {
+        ids: args
+            .get_many::<String>("taskid")
+            .map(|ids| ids.cloned().collect())
+            .unwrap_or_default(),
+        all: args.get_flag("all"),
+        name_regex: args.get_one::<String>("name_regex").cloned(),
+        task_type: match args.get_one::<String>("type") {
+            Some(t) => Some(TaskSelector::parse_type(t)?),
+            None => None,
+        },
+    })
+}
+
+// 启停、清理及删除任务，仅指定单个任务 id 时保持单任务输出，
+// 否则并发执行并输出每个任务的结果，存在失败时返回 BulkFailed
+fn run_task_op(op: BulkOp, args: &ArgMatches) -> Result<()> {
+    let selector = task_selector(args)?;
+    let client = ApiClient::current()?;
+    let ids = GLOBAL_RUNTIME.block_on(async {
+        let tasks = match selector.need_task_list() {
+            true => client.list_all_tasks().await?,
+            false => vec![],
+        };
+        selector.select(&tasks)
+    })?;
+
+    if !selector.need_task_list() && ids.task_ids.len().eq(&1) {
+        let id = TaskId {
+            task_id: ids.task_ids[0].clone(),
+        };
+        GLOBAL_RUNTIME.block_on(async { client.task_op(op, &id).await })?;
+        match op {
+            BulkOp::Start => eprintln!("start {} ok", id.task_id),
+            BulkOp::Stop => eprintln!("stop {} ok", id.task_id),
+            BulkOp::Clean => eprintln!("task {} cleaned", id.task_id),
+            BulkOp::Remove => eprintln!("task {} removed", id.task_id),
+        }
+        return Ok(());
+    }
+
+    let total = ids.task_ids.len();
+    if total.eq(&0) {
+        eprintln!("no task matched");
+        return Ok(());
+    }
+
+    let destructive = matches!(op, BulkOp::Clean | BulkOp::Remove);
+    if destructive
+        && total.gt(&1)
+        && !args.get_flag("yes")
+        && !confirm(&format!("{} {} tasks, continue? [y/N] ", op.name(), total))
+    {
+        return Err(CliError::Canceled.into());
+    }
+
+    let format = output_format(args, OutputFormat::Table)?;
+    let results = GLOBAL_RUNTIME.block_on(async { client.task_bulk(op, &ids).await });
+    print_bulk_results(&results, format)?;
+    eprintln!("{} {} tasks ok", op.name(), total);
+    Ok(())
+}
+
+// 输出逐个任务的执行结果，存在失败的任务时返回 BulkFailed
+fn print_bulk_results(results: &[BulkResult], format: OutputFormat) -> Result<()> {
+    let output = format_output(results, format, |_| {
+        let mut builder = Builder::default();
+        for r in results.iter() {
+            let result = match r.ok {
+                true => "ok",
+                false => "failed",
+            };
+            builder.push_record([
+                r.task_id.clone(),
+                result.to_string(),
+                r.error.clone().unwrap_or_default(),
+            ]);
+        }
+        builder.insert_record(0, ["id", "result", "error"]);
+        builder.build().to_string()
+    })?;
+    println!("{}", output);
+
+    let failed = results.iter().filter(|r| !r.ok).count();
+    if failed.gt(&0) {
+        return Err(CliError::BulkFailed {
+            failed,
+            total: results.len(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+// 导出任务定义，仅指定任务 id 时逐个获取任务描述，否则从全部任务中筛选
+fn export_tasks(args: &ArgMatches) -> Result<()> {
+    let selector = task_selector(args)?;
+    let client = ApiClient::current()?;
+    let tasks = GLOBAL_RUNTIME.block_on(async {
+        if selector.need_task_list() {
+            let list = client.list_all_tasks().await?;
+            let ids = selector.select(&list)?;
+            return Ok(list
+                .into_iter()
+                .map(|t| t.task)
+                .filter(|t| ids.task_ids.contains(&t.task_id()))
+                .collect());
+        }
+        let mut tasks = vec![];
+        for id in selector.ids.iter() {
+            let task_id = TaskId {
+                task_id: id.clone(),
+            };
+            tasks.push(client.task_show(&task_id).await?);
+        }
+        Ok::<Vec<Task>, anyhow::Error>(tasks)
+    })?;
+
+    let total = tasks.len();
+    let (file, json) = export_target(args);
+    // 明文凭据不输出到终端，避免泄露到终端记录、CI 日志及管道
+    if file.is_none() && !args.get_flag("show_secrets") && tasks.iter().any(has_plain_secrets) {
+        return Err(anyhow!(
+            "tasks contain plain text credentials, write the bundle to a file with -f or pass --show-secrets"
+        ));
+    }
+    let bundle = TaskBundle::new(&client.server().url, tasks);
+    let content = match json {
+        true => struct_to_json_string_prettry(&bundle)?,
+        false => struct_to_yaml_string(&bundle)?,
+    };
+    match file {
+        Some(f) => {
+            fs::write(f, content)?;
+            eprintln!("{} tasks exported to {}", total, f);
+        }
+        None => println!("{}", content.trim_end()),
+    }
+    Ok(())
+}
+
+// 导出文件路径及是否输出 json，未指定 -o 时按文件扩展名判断
+fn export_target(args: &ArgMatches) -> (Option<&String>, bool) {
+    let file = args.get_one::<String>("file");
+    let json = match args.get_one::<String>("output") {
+        Some(o) => o.eq("json"),
+        None => file.is_some_and(|f| f.ends_with(".json")),
+    };
+    (file, json)
+}
+
+// 从导出文件逐个创建任务，id 冲突的任务跳过，存在失败时返回 BulkFailed
+fn import_tasks(args: &ArgMatches) -> Result<()> {
+    let path = match args.get_one::<String>("bundle") {
+        Some(p) => p,
+        None => return Err(anyhow!("bundle file required")),
+    };
+    let bundle = TaskBundle::parse(&read_file_or_stdin(path)?)?;
+    let opts = ImportOptions {
+        new_ids: args.get_flag("new_ids"),
+        meta_dir: args.get_one::<String>("meta_dir").cloned(),
+    };
+    let format = output_format(args, OutputFormat::Table)?;
+    let client = ApiClient::current()?;
+    let existing = GLOBAL_RUNTIME
+        .block_on(async { client.list_all_tasks().await })?
+        .into_iter()
+        .map(|t| t.task.task_id())
+        .collect::<HashSet<String>>();
+
+    let assume_yes = args.get_flag("yes");
+    let mut results = vec![];
+    for item in prepare_import(bundle.tasks, &existing, &opts) {
+        let created = if item.conflict {
+            Err(anyhow!(
+                "task id {} exists, use --new-ids to import",
+                item.source_id
+            ))
+        } else if !confirm_truncate(&item.task, assume_yes) {
+            Err(CliError::Canceled.into())
+        } else {
+            resolve_task_secrets(&item.task).and_then(|t| {
+                GLOBAL_RUNTIME
+                    .block_on(async { client.task_create(&t).await })
+                    .map_err(anyhow::Error::from)
+            })
+        };
+        let (task_id, error) = match created {
+            Ok(id) => (Some(id.task_id), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        results.push(ImportResultItem {
+            source_id: item.source_id,
+            task_id,
+            name: item.task.task_name(),
+            error,
+        });
+    }
+
+    let output = format_output(&results, format, |_| {
+        let mut builder = Builder::default();
+        for r in results.iter() {
+            let result = match r.error {
+                None => "ok",
+                Some(_) => "failed",
+            };
+            builder.push_record([
+                r.source_id.clone(),
+                r.task_id.clone().unwrap_or_default(),
+                r.name.clone(),
+                result.to_string(),
+                r.error.clone().unwrap_or_default(),
+            ]);
+        }
+        builder.insert_record(0, ["source id", "task id", "name", "result", "error"]);
+        builder.build().to_string()
+    })?;
+    println!("{}", output);
+
+    let total = results.len();
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if failed.gt(&0) {
+        return Err(CliError::BulkFailed { failed, total }.into());
+    }
+    eprintln!("{} tasks imported", total);
+    Ok(())
+}
+
+// 获取源为本地目录的 transfer 任务及其源目录
+fn local_source_transfer_task(task: &Task) -> Result<(&TransferTask, &str)> {
+    let transfer = match task {
+        Task::Transfer(t) => t,
+        _ => return Err(anyhow!("only transfer task supported")),
+    };
+    let folder = match &transfer.source {
+        ObjectStorage::Local(f) => f,
+        _ => return Err(anyhow!("task source is not local directory")),
+    };
+    Ok((transfer, folder))
+}
+
+// 本地分析 transfer 任务源目录，不依赖 server
+fn analyze_local_task(task: &Task) -> Result<FolderFilesAnalysis> {
+    let (transfer, folder) = local_source_transfer_task(task)?;
+    let regex_filter = transfer.attributes.regex_filter()?;
+    analyze_folder_files_size(
+        folder,
+        Some(regex_filter),
+        transfer.attributes.modify_filter(),
+    )
+}
+
+// 输出 transfer 任务将要同步的本地文件及目标 key，返回文件数量及总字节数
+fn preview_local_task(task: &Task, writer: &mut dyn Write) -> Result<(u64, u64)> {
+    let (transfer, folder) = local_source_transfer_task(task)?;
+    let regex_filter = Some(transfer.attributes.regex_filter()?);
+    let last_modify_filter = transfer.attributes.modify_filter();
+    let mut files = 0;
+    let mut total_bytes = 0;
+    walk_folder_files(folder, &regex_filter, &last_modify_filter, |key, len| {
+        writeln!(writer, "{}\t{}", key, transfer.target.target_key(key))?;
+        files += 1;
+        total_bytes += len;
+        Ok(())
+    })?;
+    writer.flush()?;
+    Ok((files, total_bytes))
 }
 
 #[cfg(test)]
 mod test {
-    use crate::cmd::rootcmd::{get_cmd_tree, CLIAPP};
+    use crate::cmd::rootcmd::{export_target, get_cmd_tree, CLIAPP};
 
     //cargo test cmd::rootcmd::test::test_get_command_tree -- --nocapture
     #[test]
@@ -781,4 +1539,46 @@ mod test {
         let tree = get_cmd_tree(&CLIAPP);
         println!("{}", tree);
     }
+
+    //cargo test cmd::rootcmd::test::test_export_target -- --nocapture
+    #[test]
+    fn test_export_target() {
+        let target = |args: &[&str]| {
+            let matches = CLIAPP
+                .clone()
+                .try_get_matches_from(
+                    [&["files_pipe_cli", "task", "export", "--all"], args].concat(),
+                )
+                .unwrap();
+            let export = matches
+                .subcommand_matches("task")
+                .and_then(|t| t.subcommand_matches("export"))
+                .unwrap()
+                .clone();
+            let (file, json) = export_target(&export);
+            (file.cloned(), json)
+        };
+        assert_eq!(target(&[]), (None, false));
+        assert_eq!(target(&["-o", "json"]), (None, true));
+        assert_eq!(
+            target(&["-f", "tasks.json"]),
+            (Some("tasks.json".to_string()), true)
+        );
+        assert_eq!(
+            target(&["-f", "tasks.json", "-o", "yaml"]),
+            (Some("tasks.json".to_string()), false)
+        );
+        // -o 仅接受输出格式
+        assert!(CLIAPP
+            .clone()
+            .try_get_matches_from([
+                "files_pipe_cli",
+                "task",
+                "export",
+                "--all",
+                "-o",
+                "tasks.yaml"
+            ])
+            .is_err());
+    }
 }
@@ -60,9 +60,29 @@ pub fn byte_size_usize_to_str(byte_size: usize) -> String {
         }
     };
 }
+// unix 时间戳（秒）转换为可读时间，转换失败时返回原始值
+pub fn timestamp_to_string(timestamp: i128) -> String {
+    let ts = match i64::try_from(timestamp) {
+        Ok(t) => t,
+        Err(_) => return timestamp.to_string(),
+    };
+    match time::OffsetDateTime::from_unix_timestamp(ts) {
+        Ok(t) => format!(
+            "{}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            t.year(),
+            u8::from(t.month()),
+            t.day(),
+            t.hour(),
+            t.minute(),
+            t.second()
+        ),
+        Err(_) => timestamp.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::commons::{byte_size_str_to_usize, byte_size_usize_to_str};
+    use crate::commons::{byte_size_str_to_usize, byte_size_usize_to_str, timestamp_to_string};
 
     //cargo test commons::convert::test::test_byte_size_to_usize -- --nocapture
     #[test]
@@ -77,4 +97,13 @@ mod test {
         let r = byte_size_usize_to_str(1073741823);
         println!("{:?}", r);
     }
+
+    //cargo test commons::convert::test::test_timestamp_to_string -- --nocapture
+    #[test]
+    fn test_timestamp_to_string() {
+        assert_eq!(timestamp_to_string(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(timestamp_to_string(1703042173), "2023-12-20 03:16:13 UTC");
+        let big = i128::MAX;
+        assert_eq!(timestamp_to_string(big), big.to_string());
+    }
 }
@@ -1,7 +1,8 @@
 use super::{rand_util::rand_string, size_distributed, LastModifyFilter, RegexFilter};
 use anyhow::Result;
-use dashmap::DashMap;
+use serde::Serialize;
 use std::{
+    collections::BTreeMap,
     fs::{self, File, OpenOptions},
     io::{self, BufRead, LineWriter, Read, Write},
     path::Path,
@@ -23,6 +24,20 @@ where
     Ok(io::BufReader::new(file).lines())
 }
 
+// 读取文件内容，path 为 "-" 时从标准输入读取
+pub fn read_file_or_stdin(path: &str) -> Result<String> {
+    let mut content = String::new();
+    match path.eq("-") {
+        true => {
+            io::stdin().read_to_string(&mut content)?;
+        }
+        false => {
+            File::open(path)?.read_to_string(&mut content)?;
+        }
+    }
+    Ok(content)
+}
+
 pub fn copy_file(
     source: &str,
     target: &str,
@@ -87,12 +102,23 @@ fn remove_dir_contents<P: AsRef<Path>>(path: P) -> io::Result<()> {
     Ok(())
 }
 
-pub fn analyze_folder_files_size(
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FolderFilesAnalysis {
+    pub files: i128,
+    pub total_bytes: i128,
+    pub size_distributed: BTreeMap<String, i128>,
+}
+
+// 遍历目录下符合过滤条件的文件，正则匹配文件完整路径，key 为相对于 folder 的路径
+pub fn walk_folder_files<F>(
     folder: &str,
-    regex_filter: Option<RegexFilter>,
-    last_modify_filter: Option<LastModifyFilter>,
-) -> Result<DashMap<String, i128>> {
-    let size_map = DashMap::<String, i128>::new();
+    regex_filter: &Option<RegexFilter>,
+    last_modify_filter: &Option<LastModifyFilter>,
+    mut handle: F,
+) -> Result<()>
+where
+    F: FnMut(&str, u64) -> Result<()>,
+{
     for entry in WalkDir::new(folder)
         .into_iter()
         .filter_map(Result::ok)
@@ -103,34 +129,46 @@ pub fn analyze_folder_files_size(
                 continue;
             }
 
-            if let Some(f) = &regex_filter {
+            if let Some(f) = regex_filter {
                 if !f.filter(p) {
                     continue;
                 }
             }
 
-            if let Some(f) = &last_modify_filter {
-                let modified_time = entry
-                    .metadata()?
-                    .modified()?
-                    .duration_since(UNIX_EPOCH)?
-                    .as_secs();
+            let key = match p.strip_prefix(folder) {
+                Some(k) => k.trim_start_matches('/'),
+                None => p,
+            };
+
+            let metadata = entry.metadata()?;
+            if let Some(f) = last_modify_filter {
+                let modified_time = metadata.modified()?.duration_since(UNIX_EPOCH)?.as_secs();
                 if !f.filter(i128::from(modified_time)) {
                     continue;
                 }
             }
 
-            let obj_size = i128::from(entry.metadata()?.len());
-            let key = size_distributed(obj_size);
-            let mut size = match size_map.get(&key) {
-                Some(m) => *m.value(),
-                None => 0,
-            };
-            size += 1;
-            size_map.insert(key, size);
+            handle(key, metadata.len())?;
         };
     }
-    Ok(size_map)
+    Ok(())
+}
+
+pub fn analyze_folder_files_size(
+    folder: &str,
+    regex_filter: Option<RegexFilter>,
+    last_modify_filter: Option<LastModifyFilter>,
+) -> Result<FolderFilesAnalysis> {
+    let mut analysis = FolderFilesAnalysis::default();
+    walk_folder_files(folder, &regex_filter, &last_modify_filter, |_, len| {
+        let obj_size = i128::from(len);
+        let key = size_distributed(obj_size);
+        *analysis.size_distributed.entry(key).or_insert(0) += 1;
+        analysis.files += 1;
+        analysis.total_bytes += obj_size;
+        Ok(())
+    })?;
+    Ok(analysis)
 }
 
 // 生成指定字节数的文件
@@ -387,7 +425,10 @@ pub fn gen_file_part_plan(file_path: &str, chunk_size: usize) -> Result<Vec<File
 
 #[cfg(test)]
 mod test {
-    use crate::commons::{fileutiles::generate_file, fill_file_with_zero, multi_parts_copy_file};
+    use crate::commons::{
+        analyze_folder_files_size, fileutiles::generate_file, fill_file_with_zero,
+        multi_parts_copy_file, RegexFilter,
+    };
 
     use super::generate_line_file;
 
@@ -418,4 +459,26 @@ mod test {
         let r = fill_file_with_zero(1024 * 1024 * 1024 * 10, 1024 * 1024, "/tmp/zero_file");
         println!("test_fill_file_with_zero {:?}", r);
     }
+
+    //cargo test commons::fileutiles::test::test_analyze_folder_files_size -- --nocapture
+    #[test]
+    fn test_analyze_folder_files_size() {
+        let dir = "/tmp/gen/analyze_folder";
+        let _ = std::fs::remove_dir_all(dir);
+        let _ = generate_file(128, 8, "/tmp/gen/analyze_folder/t1/a");
+        let _ = generate_file(1024 * 1024 * 2, 1024, "/tmp/gen/analyze_folder/t1/b");
+        let _ = generate_file(64, 8, "/tmp/gen/analyze_folder/t2/c");
+
+        // 正则匹配完整路径，相对路径锚定的正则不生效
+        let filter = RegexFilter::from_vec(&Some(vec!["^t2/".to_string()]), &None).unwrap();
+        let r = analyze_folder_files_size(dir, Some(filter), None).unwrap();
+        assert_eq!(r.files, 3);
+
+        let filter = RegexFilter::from_vec(&Some(vec!["/t2/".to_string()]), &None).unwrap();
+        let r = analyze_folder_files_size(dir, Some(filter), None).unwrap();
+        assert_eq!(r.files, 2);
+        assert_eq!(r.total_bytes, 128 + 1024 * 1024 * 2);
+        assert_eq!(r.size_distributed.get("0-1M"), Some(&1));
+        assert_eq!(r.size_distributed.get("1-10M"), Some(&1));
+    }
 }
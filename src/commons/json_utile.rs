@@ -1,6 +1,6 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::{de, Deserialize};
-use serde_json::from_str;
+use serde_json::{from_str, Value};
 use std::fs;
 
 #[allow(dead_code)]
@@ -37,3 +37,189 @@ where
     let r = from_str::<T>(contents.as_str())?;
     Ok(r)
 }
+
+// 解析 'a.b.c=value' 形式的赋值，value 保留原始文本，由 assignment_value 按属性类型转换
+pub fn parse_path_assignment(s: &str) -> Result<(String, String)> {
+    match s.split_once('=') {
+        Some((p, v)) if !p.trim().is_empty() => Ok((p.trim().to_string(), v.to_string())),
+        _ => Err(anyhow!("invalid assignment {}, expect 'path=value'", s)),
+    }
+}
+
+// 将赋值文本转换为已有属性的 json 类型：字符串属性保留原始文本，数字及布尔属性需可解析，
+// 属性不存在或为 null 时按 yaml 标量解析。null 表示清空属性，带引号的值按字符串处理
+pub fn assignment_value(existing: Option<&Value>, raw: &str) -> Result<Value> {
+    let parsed = serde_yaml::from_str::<Value>(raw).unwrap_or(Value::String(raw.to_string()));
+    if parsed.is_null() {
+        return Ok(parsed);
+    }
+    match (existing, parsed) {
+        (Some(Value::String(_)), Value::String(s)) => Ok(Value::String(s)),
+        (Some(Value::String(_)), _) => Ok(Value::String(raw.trim().to_string())),
+        (Some(Value::Number(_)), Value::Number(n)) => Ok(Value::Number(n)),
+        (Some(Value::Bool(_)), Value::Bool(b)) => Ok(Value::Bool(b)),
+        (Some(Value::Number(_)), _) => Err(anyhow!("{} is not a number", raw)),
+        (Some(Value::Bool(_)), _) => Err(anyhow!("{} is not a boolean", raw)),
+        (_, parsed) => Ok(parsed),
+    }
+}
+
+// 按 '.' 分隔的路径获取 json 节点，数组使用数字下标
+pub fn get_json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |v, key| match v {
+        Value::Object(map) => map.get(key),
+        Value::Array(arr) => key.parse::<usize>().ok().and_then(|i| arr.get(i)),
+        _ => None,
+    })
+}
+
+// 按路径设置 json 节点，中间节点为 null 或不存在时创建对象
+pub fn set_json_path(value: &mut Value, path: &str, new: Value) -> Result<()> {
+    let mut current = value;
+    let keys = path.split('.').collect::<Vec<&str>>();
+    for (idx, key) in keys.iter().enumerate() {
+        if current.is_null() {
+            *current = Value::Object(serde_json::Map::new());
+        }
+        let walked = keys[..idx].join(".");
+        current = match current {
+            Value::Object(map) => map.entry(key.to_string()).or_insert(Value::Null),
+            Value::Array(arr) => match key.parse::<usize>().ok().and_then(|i| arr.get_mut(i)) {
+                Some(v) => v,
+                None => return Err(anyhow!("{}: index {} out of range", path, key)),
+            },
+            _ => return Err(anyhow!("{}: {} is not an object", path, walked)),
+        };
+    }
+    *current = new;
+    Ok(())
+}
+
+// 按 RFC 7396 合并 patch，patch 中值为 null 的属性从 target 中删除
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let patch_map = match patch {
+        Value::Object(map) => map,
+        _ => {
+            *target = patch.clone();
+            return;
+        }
+    };
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    if let Value::Object(map) = target {
+        for (key, value) in patch_map {
+            match value.is_null() {
+                true => {
+                    map.remove(key);
+                }
+                false => merge_patch(map.entry(key.clone()).or_insert(Value::Null), value),
+            }
+        }
+    }
+}
+
+// json 中非 null 叶子节点的路径，数组视为叶子节点
+pub fn json_leaf_paths(value: &Value) -> Vec<String> {
+    fn walk(value: &Value, prefix: &str, paths: &mut Vec<String>) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (key, v) in map {
+                    let path = match prefix.is_empty() {
+                        true => key.clone(),
+                        false => format!("{}.{}", prefix, key),
+                    };
+                    walk(v, &path, paths);
+                }
+            }
+            Value::Null => {}
+            _ => paths.push(prefix.to_string()),
+        }
+    }
+    let mut paths = vec![];
+    walk(value, "", &mut paths);
+    paths
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        assignment_value, get_json_path, json_leaf_paths, merge_patch, parse_path_assignment,
+        set_json_path,
+    };
+    use serde_json::{json, Value};
+
+    //cargo test commons::json_utile::test::test_set_json_path -- --nocapture
+    #[test]
+    fn test_set_json_path() {
+        let mut value =
+            json!({"target": {"prefix": "old/"}, "source": "/tmp", "filter": null, "list": [1, 2]});
+        set_json_path(&mut value, "target.prefix", json!("new/")).unwrap();
+        set_json_path(&mut value, "attributes.task_parallelism", json!(32)).unwrap();
+        set_json_path(&mut value, "filter.timestamp", json!(1)).unwrap();
+        set_json_path(&mut value, "list.1", Value::Bool(true)).unwrap();
+        println!("{}", value);
+        assert_eq!(get_json_path(&value, "target.prefix"), Some(&json!("new/")));
+        assert_eq!(
+            get_json_path(&value, "attributes.task_parallelism"),
+            Some(&json!(32))
+        );
+        assert_eq!(get_json_path(&value, "filter.timestamp"), Some(&json!(1)));
+        assert_eq!(get_json_path(&value, "list.1"), Some(&json!(true)));
+
+        assert!(set_json_path(&mut value, "source.prefix", json!("x")).is_err());
+        assert!(set_json_path(&mut value, "list.5", json!(1)).is_err());
+        assert_eq!(
+            parse_path_assignment("target.prefix=a=b").unwrap(),
+            ("target.prefix".to_string(), "a=b".to_string())
+        );
+        assert!(parse_path_assignment("=1").is_err());
+        assert!(parse_path_assignment("name").is_err());
+    }
+
+    //cargo test commons::json_utile::test::test_assignment_value -- --nocapture
+    #[test]
+    fn test_assignment_value() {
+        let s = json!("old");
+        let n = json!(1);
+        let b = json!(false);
+        // 字符串属性保持字符串
+        assert_eq!(assignment_value(Some(&s), "2024").unwrap(), json!("2024"));
+        assert_eq!(assignment_value(Some(&s), "007").unwrap(), json!("007"));
+        assert_eq!(assignment_value(Some(&s), "true").unwrap(), json!("true"));
+        assert_eq!(assignment_value(Some(&s), "'123'").unwrap(), json!("123"));
+        assert_eq!(assignment_value(Some(&s), "\"a b\"").unwrap(), json!("a b"));
+        assert_eq!(assignment_value(Some(&s), "null").unwrap(), Value::Null);
+        assert_eq!(assignment_value(Some(&s), "'null'").unwrap(), json!("null"));
+        // 数字及布尔属性需可解析为对应类型
+        assert_eq!(assignment_value(Some(&n), "32").unwrap(), json!(32));
+        assert!(assignment_value(Some(&n), "abc").is_err());
+        assert!(assignment_value(Some(&n), "'32'").is_err());
+        assert_eq!(assignment_value(Some(&b), "true").unwrap(), json!(true));
+        assert!(assignment_value(Some(&b), "yes please").is_err());
+        // 属性不存在或为 null 时按 yaml 标量解析
+        assert_eq!(assignment_value(None, "32").unwrap(), json!(32));
+        assert_eq!(
+            assignment_value(Some(&Value::Null), "true").unwrap(),
+            json!(true)
+        );
+        assert_eq!(assignment_value(None, "new/").unwrap(), json!("new/"));
+    }
+
+    //cargo test commons::json_utile::test::test_merge_patch -- --nocapture
+    #[test]
+    fn test_merge_patch() {
+        // RFC 7396 附录示例
+        let mut target = json!({"title": "Goodbye!", "author": {"givenName": "John", "familyName": "Doe"}, "tags": ["example", "sample"], "content": "This will be unchanged"});
+        let patch = json!({"title": "Hello!", "phoneNumber": "+01-123-456-7890", "author": {"familyName": null}, "tags": ["example"]});
+        merge_patch(&mut target, &patch);
+        assert_eq!(
+            target,
+            json!({"title": "Hello!", "author": {"givenName": "John"}, "tags": ["example"], "content": "This will be unchanged", "phoneNumber": "+01-123-456-7890"})
+        );
+        assert_eq!(
+            json_leaf_paths(&patch),
+            vec!["phoneNumber", "tags", "title"]
+        );
+    }
+}
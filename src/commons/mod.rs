@@ -4,17 +4,21 @@ mod fileutiles;
 mod filters;
 mod json_utile;
 mod notify_utile;
+mod output_format;
 
 mod public_const_info;
 mod rand_util;
 mod size_distributed;
 mod subcmdcompleter;
 mod sysutiles;
+mod text_diff;
 pub mod yamlutile;
 pub use convert::*;
 pub use fileutiles::*;
 pub use filters::*;
 pub use json_utile::*;
+pub use output_format::*;
 pub use size_distributed::*;
 pub use subcmdcompleter::CommandCompleter;
 pub use subcmdcompleter::SubCmd;
+pub use text_diff::*;
@@ -0,0 +1,64 @@
+use super::{struct_to_json_string_prettry, yamlutile::struct_to_yaml_string};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+pub const OUTPUT_FORMATS: [&'static str; 4] = ["json", "yaml", "table", "wide"];
+
+// 命令输出格式，wide 为附加更多列的 table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Table,
+    Wide,
+}
+
+impl OutputFormat {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "yaml" => Ok(OutputFormat::Yaml),
+            "table" => Ok(OutputFormat::Table),
+            "wide" => Ok(OutputFormat::Wide),
+            _ => Err(anyhow!("unsupported output format '{}'", s)),
+        }
+    }
+
+    pub fn is_structured(&self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Yaml)
+    }
+}
+
+// 按输出格式渲染数据，table 与 wide 由调用方提供的闭包生成，参数为是否 wide
+pub fn format_output<T, F>(value: &T, format: OutputFormat, table: F) -> Result<String>
+where
+    T: ?Sized + Serialize,
+    F: FnOnce(bool) -> String,
+{
+    match format {
+        OutputFormat::Json => struct_to_json_string_prettry(value),
+        OutputFormat::Yaml => struct_to_yaml_string(value).map(|y| y.trim_end().to_string()),
+        OutputFormat::Table => Ok(table(false)),
+        OutputFormat::Wide => Ok(table(true)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_output, OutputFormat};
+    use std::collections::BTreeMap;
+
+    //cargo test commons::output_format::test::test_format_output -- --nocapture
+    #[test]
+    fn test_format_output() {
+        let mut map = BTreeMap::new();
+        map.insert("k", 1);
+        let json = format_output(&map, OutputFormat::Json, |_| "".to_string()).unwrap();
+        assert_eq!(json, "{\n  \"k\": 1\n}");
+        let yaml = format_output(&map, OutputFormat::Yaml, |_| "".to_string()).unwrap();
+        assert_eq!(yaml, "k: 1");
+        let wide = format_output(&map, OutputFormat::Wide, |w| w.to_string()).unwrap();
+        assert_eq!(wide, "true");
+        assert!(OutputFormat::parse("xml").is_err());
+    }
+}
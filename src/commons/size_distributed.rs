@@ -1,3 +1,20 @@
+use std::collections::BTreeMap;
+use tabled::builder::Builder;
+
+// 文件尺寸分布区间，按尺寸由小到大排列
+pub const SIZE_DISTRIBUTED_BUCKETS: [&'static str; 8] = [
+    "0-1M",
+    "1-10M",
+    "10-100M",
+    "100-300M",
+    "300-500M",
+    "500-800M",
+    "800-1024M",
+    "1G+",
+];
+
+const BAR_WIDTH: usize = 40;
+
 pub fn size_distributed(size: i128) -> String {
     return match size {
         s if s < 1024 * 1024 => "0-1M".to_string(),
@@ -10,3 +27,74 @@ pub fn size_distributed(size: i128) -> String {
         _ => "1G+".to_string(),
     };
 }
+
+// 按区间顺序排列尺寸分布，未知区间排在最后
+pub fn sorted_size_distributed(distributed: &BTreeMap<String, i128>) -> Vec<(String, i128)> {
+    let mut sorted = SIZE_DISTRIBUTED_BUCKETS
+        .iter()
+        .map(|b| {
+            let count = distributed.get(*b).copied().unwrap_or(0);
+            (b.to_string(), count)
+        })
+        .collect::<Vec<(String, i128)>>();
+
+    for (k, v) in distributed {
+        if !SIZE_DISTRIBUTED_BUCKETS.contains(&k.as_str()) {
+            sorted.push((k.clone(), *v));
+        }
+    }
+    sorted
+}
+
+// 生成尺寸分布直方图表格，with_chart 为 true 时附加字符柱状图
+pub fn size_distributed_table(distributed: &BTreeMap<String, i128>, with_chart: bool) -> String {
+    let sorted = sorted_size_distributed(distributed);
+    let total: i128 = sorted.iter().map(|(_, v)| *v).sum();
+    let max = sorted.iter().map(|(_, v)| *v).max().unwrap_or(0);
+
+    let mut builder = Builder::default();
+    for (bucket, count) in sorted {
+        let percent = match total.eq(&0) {
+            true => 0.0,
+            false => count as f64 / total as f64 * 100.0,
+        };
+        let mut raw = vec![bucket, count.to_string(), format!("{:.2}%", percent)];
+        if with_chart {
+            let width = match max.eq(&0) {
+                true => 0,
+                false => (count * BAR_WIDTH as i128 / max) as usize,
+            };
+            raw.push("#".repeat(width));
+        }
+        builder.push_record(raw);
+    }
+
+    let mut header = vec!["size", "objects", "percent"];
+    if with_chart {
+        header.push("chart");
+    }
+    builder.insert_record(0, header);
+    builder.push_record(vec!["total".to_string(), total.to_string()]);
+    builder.build().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{size_distributed_table, sorted_size_distributed};
+    use std::collections::BTreeMap;
+
+    //cargo test commons::size_distributed::test::test_sorted_size_distributed -- --nocapture
+    #[test]
+    fn test_sorted_size_distributed() {
+        let mut distributed = BTreeMap::new();
+        distributed.insert("1G+".to_string(), 2);
+        distributed.insert("0-1M".to_string(), 10);
+        distributed.insert("10-100M".to_string(), 4);
+        let sorted = sorted_size_distributed(&distributed);
+        assert_eq!(sorted.len(), 8);
+        assert_eq!(sorted[0], ("0-1M".to_string(), 10));
+        assert_eq!(sorted[1], ("1-10M".to_string(), 0));
+        assert_eq!(sorted[7], ("1G+".to_string(), 2));
+        println!("{}", size_distributed_table(&distributed, true));
+    }
+}
@@ -0,0 +1,105 @@
+// 按行比较文本的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+// 基于最长公共子序列的按行比较，适用于任务描述等小文本
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old = old.lines().collect::<Vec<&str>>();
+    let new = new.lines().collect::<Vec<&str>>();
+    let (n, m) = (old.len(), new.len());
+
+    // lcs[i][j] 为 old[i..] 与 new[j..] 的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = match old[i].eq(new[j]) {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i].eq(new[j]) {
+            lines.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    lines.extend(new[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    lines
+}
+
+// 渲染比较结果，仅保留变更行前后 context 行，color 为 true 时使用终端颜色
+pub fn format_diff(lines: &[DiffLine], context: usize, color: bool) -> String {
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, DiffLine::Same(_)))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<usize>>();
+    let visible = |idx: usize| {
+        changed
+            .iter()
+            .any(|c| idx + context >= *c && idx <= *c + context)
+    };
+
+    let mut out = vec![];
+    let mut skipped = false;
+    for (idx, line) in lines.iter().enumerate() {
+        if !visible(idx) {
+            skipped = true;
+            continue;
+        }
+        if skipped && !out.is_empty() {
+            out.push("...".to_string());
+        }
+        skipped = false;
+        let (prefix, text, code) = match line {
+            DiffLine::Same(l) => (" ", l, None),
+            DiffLine::Removed(l) => ("-", l, Some("31")),
+            DiffLine::Added(l) => ("+", l, Some("32")),
+        };
+        match (color, code) {
+            (true, Some(c)) => out.push(format!("\x1b[{}m{} {}\x1b[0m", c, prefix, text)),
+            _ => out.push(format!("{} {}", prefix, text)),
+        }
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diff_lines, format_diff, DiffLine};
+
+    //cargo test commons::text_diff::test::test_diff_lines -- --nocapture
+    #[test]
+    fn test_diff_lines() {
+        let old = "a: 1\nb: 2\nc: 3\nd: 4\ne: 5\nf: 6";
+        let new = "a: 1\nb: 2\nc: 30\nd: 4\ne: 5\nf: 6\ng: 7";
+        let lines = diff_lines(old, new);
+        assert_eq!(lines[2], DiffLine::Removed("c: 3".to_string()));
+        assert_eq!(lines[3], DiffLine::Added("c: 30".to_string()));
+        assert_eq!(lines.last(), Some(&DiffLine::Added("g: 7".to_string())));
+
+        let out = format_diff(&lines, 1, false);
+        println!("{}", out);
+        assert_eq!(out, "  b: 2\n- c: 3\n+ c: 30\n  d: 4\n...\n  f: 6\n+ g: 7");
+        assert!(diff_lines(old, old)
+            .iter()
+            .all(|l| matches!(l, DiffLine::Same(_))));
+    }
+}
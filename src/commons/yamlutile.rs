@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use serde_yaml::from_str;
+use std::collections::HashMap;
 use std::fs;
 
 pub fn struct_to_yml_file<T>(value: &T, path: &str) -> Result<()>
@@ -28,3 +29,44 @@ where
     let r = from_str::<T>(contents.as_str())?;
     Ok(r)
 }
+
+// 解析多文档 yaml，每个文档对应一个结构体
+pub fn yaml_documents_to_structs<T>(content: &str) -> Result<Vec<T>>
+where
+    T: for<'a> Deserialize<'a>,
+{
+    let mut vec = vec![];
+    for document in serde_yaml::Deserializer::from_str(content) {
+        let value = serde_yaml::Value::deserialize(document)?;
+        if value.is_null() {
+            continue;
+        }
+        vec.push(serde_yaml::from_value::<T>(value)?);
+    }
+    Ok(vec)
+}
+
+// 为 yaml 中的 key 添加注释，注释位于 key 所在行之上并保持相同缩进
+pub fn yaml_with_comments(yaml: &str, comments: &HashMap<&str, &str>) -> String {
+    let mut annotated = String::new();
+    for line in yaml.lines() {
+        let content = line.trim_start();
+        let indent = &line[..line.len() - content.len()];
+        let key = content
+            .trim_start_matches("- ")
+            .split(':')
+            .next()
+            .unwrap_or("");
+        if content.contains(':') {
+            if let Some(comment) = comments.get(key) {
+                annotated.push_str(indent);
+                annotated.push_str("# ");
+                annotated.push_str(comment);
+                annotated.push('\n');
+            }
+        }
+        annotated.push_str(line);
+        annotated.push('\n');
+    }
+    annotated
+}
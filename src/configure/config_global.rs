@@ -6,6 +6,7 @@ use std::fs;
 use std::path::Path;
 use std::sync::Mutex;
 use std::sync::RwLock;
+use std::time::Duration;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JdCloud {
@@ -47,6 +48,51 @@ impl TaskConfig {
     }
 }
 
+// 幂等请求重试策略，退避时间按指数增长，不超过 max_backoff_ms
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetryConfig {
+    pub max_retries: usize,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl RetryConfig {
+    pub fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 200,
+            max_backoff_ms: 5000,
+        }
+    }
+
+    // 第 attempt 次重试前的等待时间，attempt 从 0 开始
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let factor = 2_u64.saturating_pow(attempt.min(32) as u32);
+        let ms = self
+            .initial_backoff_ms
+            .saturating_mul(factor)
+            .min(self.max_backoff_ms);
+        Duration::from_millis(ms)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpConfig {
+    pub connect_timeout_secs: u64,
+    pub request_timeout_secs: u64,
+    pub retry: RetryConfig,
+}
+
+impl HttpConfig {
+    pub fn default() -> Self {
+        Self {
+            connect_timeout_secs: 5,
+            request_timeout_secs: 30,
+            retry: RetryConfig::default(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub threads: usize,
@@ -55,6 +101,8 @@ pub struct Config {
     pub curl_file: String,
     pub jdcloud: JdCloud,
     pub task_config: TaskConfig,
+    #[serde(default = "HttpConfig::default")]
+    pub http: HttpConfig,
 }
 
 impl Config {
@@ -66,6 +114,7 @@ impl Config {
             curl_file: "curl.json".to_string(),
             jdcloud: JdCloud::default(),
             task_config: TaskConfig::default(),
+            http: HttpConfig::default(),
         }
     }
 
@@ -76,6 +125,7 @@ impl Config {
         self.curl_file = config.curl_file;
         self.jdcloud = config.jdcloud;
         self.task_config = config.task_config;
+        self.http = config.http;
     }
 
     pub fn get_config_image(&self) -> Self {
@@ -0,0 +1,162 @@
+use thiserror::Error;
+
+// 进程退出码，供脚本区分失败原因
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_GENERAL_ERROR: i32 = 1;
+pub const EXIT_USAGE_ERROR: i32 = 2;
+pub const EXIT_TRANSPORT_ERROR: i32 = 3;
+pub const EXIT_TIMEOUT: i32 = 4;
+pub const EXIT_HTTP_STATUS_ERROR: i32 = 5;
+pub const EXIT_DECODE_ERROR: i32 = 6;
+pub const EXIT_SERVER_ERROR: i32 = 7;
+pub const EXIT_MISSING_DATA: i32 = 8;
+pub const EXIT_INVALID_SERVER_URL: i32 = 9;
+pub const EXIT_VALIDATION_FAILED: i32 = 10;
+pub const EXIT_CANCELED: i32 = 11;
+pub const EXIT_TLS_CONFIG_ERROR: i32 = 12;
+pub const EXIT_TASK_BROKEN: i32 = 13;
+pub const EXIT_BULK_FAILED: i32 = 14;
+pub const EXIT_TASK_STOP_UNKNOWN: i32 = 15;
+
+// 访问 server 时的错误
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("invalid server url '{0}'")]
+    InvalidServerUrl(String),
+    #[error("server unreachable: {0}")]
+    Transport(String),
+    #[error("request timeout: {0}")]
+    Timeout(String),
+    #[error("http status {status}: {body}")]
+    HttpStatus { status: u16, body: String },
+    #[error("decode response error: {0}")]
+    Decode(String),
+    #[error("server error, code: {code}, msg: {msg}")]
+    Server { code: i32, msg: String },
+    #[error("response data missing")]
+    MissingData,
+    #[error("invalid tls config: {0}")]
+    TlsConfig(String),
+}
+
+impl ClientError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ClientError::InvalidServerUrl(_) => EXIT_INVALID_SERVER_URL,
+            ClientError::Transport(_) => EXIT_TRANSPORT_ERROR,
+            ClientError::Timeout(_) => EXIT_TIMEOUT,
+            ClientError::HttpStatus { .. } => EXIT_HTTP_STATUS_ERROR,
+            ClientError::Decode(_) => EXIT_DECODE_ERROR,
+            ClientError::Server { .. } => EXIT_SERVER_ERROR,
+            ClientError::MissingData => EXIT_MISSING_DATA,
+            ClientError::TlsConfig(_) => EXIT_TLS_CONFIG_ERROR,
+        }
+    }
+
+    // 连接失败、超时、429 及 5xx 状态码可能为瞬时错误，允许重试
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Transport(_) | ClientError::Timeout(_) => true,
+            ClientError::HttpStatus { status, .. } => status.eq(&429) || status.ge(&500),
+            _ => false,
+        }
+    }
+}
+
+// 命令执行过程中非 server 相关的错误
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("task validation failed, {0} violations")]
+    ValidationFailed(usize),
+    #[error("operation canceled")]
+    Canceled,
+    #[error("task {0} stopped by errors")]
+    TaskBroken(String),
+    #[error("{failed} of {total} tasks failed")]
+    BulkFailed { failed: usize, total: usize },
+    #[error("task {0} stopped, stop reason unknown")]
+    TaskStopUnknown(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::ValidationFailed(_) => EXIT_VALIDATION_FAILED,
+            CliError::Canceled => EXIT_CANCELED,
+            CliError::TaskBroken(_) => EXIT_TASK_BROKEN,
+            CliError::BulkFailed { .. } => EXIT_BULK_FAILED,
+            CliError::TaskStopUnknown(_) => EXIT_TASK_STOP_UNKNOWN,
+        }
+    }
+}
+
+// 根据错误类型确定进程退出码，未归类错误返回 EXIT_GENERAL_ERROR
+pub fn exit_code(e: &anyhow::Error) -> i32 {
+    if let Some(c) = e.downcast_ref::<ClientError>() {
+        return c.exit_code();
+    }
+    if let Some(c) = e.downcast_ref::<CliError>() {
+        return c.exit_code();
+    }
+    EXIT_GENERAL_ERROR
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            return ClientError::Timeout(e.to_string());
+        }
+        if e.is_decode() {
+            return ClientError::Decode(e.to_string());
+        }
+        if let Some(status) = e.status() {
+            return ClientError::HttpStatus {
+                status: status.as_u16(),
+                body: e.to_string(),
+            };
+        }
+        ClientError::Transport(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        exit_code, CliError, ClientError, EXIT_BULK_FAILED, EXIT_CANCELED, EXIT_GENERAL_ERROR,
+        EXIT_MISSING_DATA, EXIT_SERVER_ERROR, EXIT_TASK_BROKEN, EXIT_TASK_STOP_UNKNOWN,
+    };
+    use anyhow::anyhow;
+
+    //cargo test exception::client_errors::test::test_client_error_exit_code -- --nocapture
+    #[test]
+    fn test_client_error_exit_code() {
+        let server_err = ClientError::Server {
+            code: 1,
+            msg: "task not exists".to_string(),
+        };
+        println!("{}", server_err);
+        assert_eq!(server_err.exit_code(), EXIT_SERVER_ERROR);
+        assert_eq!(ClientError::MissingData.exit_code(), EXIT_MISSING_DATA);
+        assert_eq!(exit_code(&server_err.into()), EXIT_SERVER_ERROR);
+        assert_eq!(exit_code(&CliError::Canceled.into()), EXIT_CANCELED);
+        assert_eq!(
+            exit_code(&CliError::TaskBroken("1".to_string()).into()),
+            EXIT_TASK_BROKEN
+        );
+        assert_eq!(
+            exit_code(&CliError::TaskStopUnknown("1".to_string()).into()),
+            EXIT_TASK_STOP_UNKNOWN
+        );
+        assert_eq!(
+            exit_code(
+                &CliError::BulkFailed {
+                    failed: 1,
+                    total: 2
+                }
+                .into()
+            ),
+            EXIT_BULK_FAILED
+        );
+        assert_eq!(exit_code(&anyhow!("io error")), EXIT_GENERAL_ERROR);
+    }
+}
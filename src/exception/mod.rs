@@ -1,2 +1,4 @@
+mod client_errors;
 mod transfor_errors;
+pub use client_errors::*;
 pub use transfor_errors::*;
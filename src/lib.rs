@@ -0,0 +1,7 @@
+// files_pipe 客户端库，cli 及 tui 基于该库实现
+// 其他 rust 服务可通过 request::ApiClient 及 request 中的任务类型以编程方式管理迁移任务
+pub mod commons;
+pub mod configure;
+pub mod exception;
+pub mod request;
+pub mod resources;
@@ -1,15 +1,11 @@
 use crate::logger::init_log;
+use files_pipe_cli::{commons, configure, exception, request, resources};
 use logger::tracing_init;
 use request::GLOBAL_RUNTIME;
 use serde::{Deserialize, Serialize};
 mod cmd;
-mod commons;
-mod configure;
-mod exception;
 mod interact;
 mod logger;
-mod request;
-mod resources;
 mod tui;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -0,0 +1,197 @@
+use super::{
+    api_url, http_client, send_idempotent_request, send_request, Response, TaskServer,
+    GLOBAL_CURRENT_SERVER,
+};
+use crate::exception::ClientError;
+use reqwest::{Client, Method, RequestBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+impl From<HttpMethod> for Method {
+    fn from(m: HttpMethod) -> Self {
+        match m {
+            HttpMethod::Get => Method::GET,
+            HttpMethod::Post => Method::POST,
+        }
+    }
+}
+
+// server api 描述，Req 为请求体类型，Resp 为响应 data 类型
+#[derive(Debug)]
+pub struct Endpoint<Req, Resp> {
+    pub method: HttpMethod,
+    pub path: &'static str,
+    // 幂等请求失败时允许重试
+    pub idempotent: bool,
+    _marker: PhantomData<fn(Req) -> Resp>,
+}
+
+impl<Req, Resp> Endpoint<Req, Resp> {
+    pub const fn get(path: &'static str) -> Self {
+        Self {
+            method: HttpMethod::Get,
+            path,
+            idempotent: true,
+            _marker: PhantomData,
+        }
+    }
+
+    pub const fn post(path: &'static str) -> Self {
+        Self {
+            method: HttpMethod::Post,
+            path,
+            idempotent: false,
+            _marker: PhantomData,
+        }
+    }
+
+    // 只读的 post 请求
+    pub const fn post_idempotent(path: &'static str) -> Self {
+        Self {
+            method: HttpMethod::Post,
+            path,
+            idempotent: true,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// 绑定单个 server 的 api client，不依赖全局状态，可同时访问多个 server
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    server: TaskServer,
+    client: Client,
+}
+
+impl ApiClient {
+    pub fn new(server: TaskServer) -> Result<Self, ClientError> {
+        let client = http_client(&server.tls)?;
+        Ok(Self { server, client })
+    }
+
+    // 使用当前 server 创建 client
+    pub fn current() -> Result<Self, ClientError> {
+        let server = GLOBAL_CURRENT_SERVER.read().unwrap().clone();
+        Self::new(server)
+    }
+
+    pub fn server(&self) -> &TaskServer {
+        &self.server
+    }
+
+    // 发送带请求体的请求
+    pub async fn call<Req, Resp>(
+        &self,
+        endpoint: &Endpoint<Req, Resp>,
+        req: &Req,
+    ) -> Result<Response<Resp>, ClientError>
+    where
+        Req: Serialize,
+        Resp: DeserializeOwned + Serialize,
+    {
+        let builder = self.request(endpoint)?.json(req);
+        self.send(endpoint, builder).await
+    }
+
+    // 发送无请求体的请求
+    pub async fn call_empty<Resp>(
+        &self,
+        endpoint: &Endpoint<(), Resp>,
+    ) -> Result<Response<Resp>, ClientError>
+    where
+        Resp: DeserializeOwned + Serialize,
+    {
+        let builder = self.request(endpoint)?;
+        self.send(endpoint, builder).await
+    }
+
+    fn request<Req, Resp>(
+        &self,
+        endpoint: &Endpoint<Req, Resp>,
+    ) -> Result<RequestBuilder, ClientError> {
+        let url = api_url(&self.server.url, endpoint.path)?;
+        let builder = self.client.request(endpoint.method.into(), url);
+        Ok(self.server.auth.apply(builder))
+    }
+
+    async fn send<Req, Resp>(
+        &self,
+        endpoint: &Endpoint<Req, Resp>,
+        builder: RequestBuilder,
+    ) -> Result<Response<Resp>, ClientError>
+    where
+        Resp: DeserializeOwned + Serialize,
+    {
+        match endpoint.idempotent {
+            true => send_idempotent_request::<Resp>(builder).await,
+            false => send_request::<Resp>(builder).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ApiClient, Endpoint};
+    use crate::request::test_server::{ok_body, serve};
+    use crate::request::{ServerAuth, TaskId, TaskServer};
+    use std::net::TcpListener;
+    use tokio::runtime;
+
+    const ENDPOINT_ECHO: Endpoint<TaskId, String> = Endpoint::post("/api/v1/echo");
+
+    fn local_server(name: &'static str) -> (TaskServer, std::thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let task_server = TaskServer {
+            name: name.to_string(),
+            url: format!("http://{}", listener.local_addr().unwrap()),
+            ..Default::default()
+        };
+        // 应答一次请求，data 为 name 及请求头中的 authorization
+        let server = serve(listener, 1, move |req| {
+            let req = req.to_lowercase();
+            let auth = req
+                .lines()
+                .find_map(|l| l.strip_prefix("authorization: "))
+                .unwrap_or("none");
+            (200, ok_body(&format!(r#""{} {}""#, name, auth)))
+        });
+        (task_server, server)
+    }
+
+    //cargo test request::api_client::test::test_api_client_multi_server -- --nocapture
+    #[test]
+    fn test_api_client_multi_server() {
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let (server_a, handle_a) = local_server("a");
+        let (mut server_b, handle_b) = local_server("b");
+        server_b.auth = ServerAuth {
+            bearer_token: Some("token".to_string()),
+            ..Default::default()
+        };
+
+        let client_a = ApiClient::new(server_a).unwrap();
+        let client_b = ApiClient::new(server_b).unwrap();
+        let id = TaskId {
+            task_id: "1".to_string(),
+        };
+        let (a, b) = rt.block_on(async {
+            tokio::join!(
+                client_a.call(&ENDPOINT_ECHO, &id),
+                client_b.call(&ENDPOINT_ECHO, &id)
+            )
+        });
+        assert_eq!(a.unwrap().into_data().unwrap(), "a none");
+        assert_eq!(b.unwrap().into_data().unwrap(), "b bearer token");
+        handle_a.join().unwrap();
+        handle_b.join().unwrap();
+    }
+}
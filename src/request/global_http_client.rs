@@ -1,8 +1,16 @@
+use super::{ServerAuth, ServerTls};
+use crate::{
+    configure::{get_config, HttpConfig},
+    exception::ClientError,
+};
 use anyhow::Result;
 use once_cell::sync::Lazy;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 use tokio::runtime::{self, Runtime};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -10,6 +18,8 @@ use tokio::runtime::{self, Runtime};
 pub struct TaskServer {
     pub name: String,
     pub url: String,
+    pub auth: ServerAuth,
+    pub tls: ServerTls,
 }
 
 impl TaskServer {
@@ -29,6 +39,8 @@ impl Default for TaskServer {
         Self {
             name: "default_name".to_string(),
             url: "http://127.0.0.1:3000".to_string(),
+            auth: ServerAuth::default(),
+            tls: ServerTls::default(),
         }
     }
 }
@@ -41,14 +53,42 @@ pub static GLOBAL_RUNTIME: Lazy<Arc<Runtime>> = Lazy::new(|| {
     Arc::new(runtime)
 });
 
-pub static GLOBAL_HTTP_CLIENT: Lazy<Arc<Client>> = Lazy::new(|| {
-    // let client = reqwest::Client::new();
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
-        .build()
-        .unwrap();
-    Arc::new(client)
-});
+// http client 缓存键，tls 及超时配置相同的 server 共用同一 client
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientKey {
+    pub tls: ServerTls,
+    pub connect_timeout_secs: u64,
+    pub request_timeout_secs: u64,
+}
+
+pub static GLOBAL_HTTP_CLIENTS: Lazy<RwLock<HashMap<ClientKey, Client>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub fn http_client(tls: &ServerTls) -> Result<Client, ClientError> {
+    let http = http_config();
+    let key = ClientKey {
+        tls: tls.clone(),
+        connect_timeout_secs: http.connect_timeout_secs,
+        request_timeout_secs: http.request_timeout_secs,
+    };
+    if let Some(client) = GLOBAL_HTTP_CLIENTS.read().unwrap().get(&key) {
+        return Ok(client.clone());
+    }
+    let client = tls.build_client(&http)?;
+    GLOBAL_HTTP_CLIENTS
+        .write()
+        .unwrap()
+        .insert(key, client.clone());
+    Ok(client)
+}
+
+// 配置读取失败时使用默认配置
+pub fn http_config() -> HttpConfig {
+    match get_config() {
+        Ok(c) => c.http,
+        Err(_) => HttpConfig::default(),
+    }
+}
 
 fn init_task_runtime() -> Result<Runtime> {
     let rt = runtime::Builder::new_multi_thread()
@@ -1,10 +1,32 @@
+mod api_client;
 mod global_http_client;
 mod modules;
 mod req;
 mod req_task;
 mod req_template;
+mod server_auth;
+mod server_tls;
+mod task_bulk;
+mod task_bundle;
+mod task_clone;
+mod task_patch;
+mod task_secret;
+mod task_template;
+mod task_validate;
+#[cfg(test)]
+mod test_server;
+pub use api_client::*;
 pub use global_http_client::*;
 pub use modules::*;
 pub use req::*;
 pub use req_task::*;
 pub use req_template::*;
+pub use server_auth::*;
+pub use server_tls::*;
+pub use task_bulk::*;
+pub use task_bundle::*;
+pub use task_clone::*;
+pub use task_patch::*;
+pub use task_secret::*;
+pub use task_template::*;
+pub use task_validate::*;
@@ -2,10 +2,16 @@ mod module_filter;
 mod module_respose;
 mod module_storage;
 mod module_task;
+mod module_task_checkpoint;
 mod module_task_compare;
 mod module_task_status;
+mod module_task_truncate;
 mod modules;
 pub use module_respose::*;
+pub use module_storage::*;
 pub use module_task::*;
+pub use module_task_checkpoint::*;
+pub use module_task_compare::*;
 pub use module_task_status::*;
+pub use module_task_truncate::*;
 pub use modules::*;
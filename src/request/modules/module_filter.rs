@@ -1,3 +1,4 @@
+use crate::commons;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
@@ -20,3 +21,16 @@ pub struct LastModifyFilter {
 //         }
 //     }
 // }
+
+impl From<LastModifyFilter> for commons::LastModifyFilter {
+    fn from(f: LastModifyFilter) -> Self {
+        let filter_type = match f.filter_type {
+            LastModifyFilterType::Greater => commons::LastModifyFilterType::Greater,
+            LastModifyFilterType::Less => commons::LastModifyFilterType::Less,
+        };
+        Self {
+            filter_type,
+            timestamp: i128::from(f.timestamp),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::exception::ClientError;
 use serde::{Deserialize, Serialize};
 
 // 自定义响应
@@ -22,3 +23,24 @@ where
         Self::new(code, msg, None)
     }
 }
+
+impl<T> Response<T>
+where
+    T: Serialize,
+{
+    // code 非 0 时转换为 server 业务错误
+    pub fn into_result(self) -> Result<Option<T>, ClientError> {
+        match self.code.eq(&0) {
+            true => Ok(self.data),
+            false => Err(ClientError::Server {
+                code: self.code,
+                msg: self.msg,
+            }),
+        }
+    }
+
+    // 业务成功且必须携带 data
+    pub fn into_data(self) -> Result<T, ClientError> {
+        self.into_result()?.ok_or(ClientError::MissingData)
+    }
+}
@@ -1,4 +1,6 @@
+use crate::request::{mask_access_key_id, mask_secret};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(untagged)]
@@ -15,7 +17,39 @@ impl Default for ObjectStorage {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+// 用于表格展示的简要描述
+impl fmt::Display for ObjectStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectStorage::Local(dir) => write!(f, "{}", dir),
+            ObjectStorage::OSS(oss) => write!(
+                f,
+                "{:?}:{}/{}",
+                oss.provider,
+                oss.bucket,
+                oss.prefix.clone().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+impl ObjectStorage {
+    // 源文件 key 在目标存储中对应的 key 或路径
+    pub fn target_key(&self, key: &str) -> String {
+        match self {
+            ObjectStorage::Local(dir) => match dir.ends_with('/') {
+                true => format!("{}{}", dir, key),
+                false => format!("{}/{}", dir, key),
+            },
+            ObjectStorage::OSS(oss) => match &oss.prefix {
+                Some(p) => format!("{}{}", p, key),
+                None => key.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct OSSDescription {
     pub provider: OssProvider,
     pub access_key_id: String,
@@ -48,6 +82,21 @@ impl OSSDescription {
     }
 }
 
+// 日志及调试输出时隐藏访问凭据
+impl fmt::Debug for OSSDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OSSDescription")
+            .field("provider", &self.provider)
+            .field("access_key_id", &mask_access_key_id(&self.access_key_id))
+            .field("secret_access_key", &mask_secret(&self.secret_access_key))
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.region)
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum OssProvider {
     JD,
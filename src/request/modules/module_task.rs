@@ -1,8 +1,11 @@
 use super::{
-    module_filter::LastModifyFilter, module_storage::ObjectStorage,
+    module_filter::LastModifyFilter,
+    module_storage::{OSSDescription, ObjectStorage},
     module_task_compare::CompareTask,
+    module_task_truncate::TruncateTask,
 };
-use crate::commons::{byte_size_str_to_usize, byte_size_usize_to_str};
+use crate::commons::{self, byte_size_str_to_usize, byte_size_usize_to_str, RegexFilter};
+use anyhow::Result;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use snowflake::SnowflakeIdGenerator;
 
@@ -71,6 +74,7 @@ impl TaskStopReason {
 pub enum Task {
     Transfer(TransferTask),
     Compare(CompareTask),
+    TruncateBucket(TruncateTask),
 }
 
 impl Task {
@@ -78,6 +82,7 @@ impl Task {
         return match self {
             Task::Transfer(transfer) => transfer.task_id.clone(),
             Task::Compare(compare) => compare.task_id.clone(),
+            Task::TruncateBucket(truncate) => truncate.task_id.clone(),
         };
     }
 
@@ -85,12 +90,14 @@ impl Task {
         return match self {
             Task::Transfer(transfer) => transfer.name.clone(),
             Task::Compare(compare) => compare.name.clone(),
+            Task::TruncateBucket(truncate) => truncate.name.clone(),
         };
     }
     pub fn task_type(&self) -> TaskType {
         match self {
             Task::Transfer(_) => TaskType::Transfer,
             Task::Compare(_) => TaskType::Compare,
+            Task::TruncateBucket(_) => TaskType::TruncateBucket,
         }
     }
 
@@ -98,6 +105,7 @@ impl Task {
         match self {
             Task::Transfer(t) => t.attributes.meta_dir.clone(),
             Task::Compare(c) => c.attributes.meta_dir.clone(),
+            Task::TruncateBucket(t) => t.attributes.meta_dir.clone(),
         }
     }
 
@@ -105,6 +113,7 @@ impl Task {
         match self {
             Task::Transfer(t) => t.source.clone(),
             Task::Compare(c) => c.target.clone(),
+            Task::TruncateBucket(t) => ObjectStorage::OSS(t.target.clone()),
         }
     }
 
@@ -112,6 +121,7 @@ impl Task {
         match self {
             Task::Transfer(t) => t.target.clone(),
             Task::Compare(c) => c.target.clone(),
+            Task::TruncateBucket(t) => ObjectStorage::OSS(t.target.clone()),
         }
     }
 
@@ -123,8 +133,42 @@ impl Task {
             Task::Compare(compare) => {
                 compare.attributes.meta_dir = meta_dir.to_string();
             }
+            Task::TruncateBucket(truncate) => {
+                truncate.attributes.meta_dir = meta_dir.to_string();
+            }
         }
     }
+    // 任务中全部对象存储描述，用于统一处理访问凭据
+    pub fn oss_descriptions(&self) -> Vec<&OSSDescription> {
+        let storages = match self {
+            Task::Transfer(t) => vec![&t.source, &t.target],
+            Task::Compare(c) => vec![&c.source, &c.target],
+            Task::TruncateBucket(t) => return vec![&t.target],
+        };
+        storages
+            .into_iter()
+            .filter_map(|s| match s {
+                ObjectStorage::OSS(oss) => Some(oss),
+                ObjectStorage::Local(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn oss_descriptions_mut(&mut self) -> Vec<&mut OSSDescription> {
+        let storages = match self {
+            Task::Transfer(t) => vec![&mut t.source, &mut t.target],
+            Task::Compare(c) => vec![&mut c.source, &mut c.target],
+            Task::TruncateBucket(t) => return vec![&mut t.target],
+        };
+        storages
+            .into_iter()
+            .filter_map(|s| match s {
+                ObjectStorage::OSS(oss) => Some(oss),
+                ObjectStorage::Local(_) => None,
+            })
+            .collect()
+    }
+
     pub fn set_task_id(&mut self, task_id: &str) {
         match self {
             Task::Transfer(transfer) => {
@@ -133,6 +177,9 @@ impl Task {
             Task::Compare(compare) => {
                 compare.task_id = task_id.to_string();
             }
+            Task::TruncateBucket(truncate) => {
+                truncate.task_id = task_id.to_string();
+            }
         }
     }
 }
@@ -187,6 +234,39 @@ pub struct TransferTaskAttributes {
     pub last_modify_filter: Option<LastModifyFilter>,
 }
 
+impl Default for TransferTaskAttributes {
+    fn default() -> Self {
+        Self {
+            objects_per_batch: TaskDefaultParameters::objects_per_batch_default(),
+            task_parallelism: TaskDefaultParameters::task_parallelism_default(),
+            max_errors: TaskDefaultParameters::max_errors_default(),
+            meta_dir: TaskDefaultParameters::meta_dir_default(),
+            target_exists_skip: TaskDefaultParameters::target_exists_skip_default(),
+            start_from_checkpoint: TaskDefaultParameters::start_from_checkpoint_default(),
+            large_file_size: TaskDefaultParameters::large_file_size_default(),
+            multi_part_chunk_size: TaskDefaultParameters::multi_part_chunk_size_default(),
+            multi_part_chunks_per_batch: TaskDefaultParameters::multi_part_chunks_per_batch_default(
+            ),
+            multi_part_parallelism: TaskDefaultParameters::multi_part_parallelism_default(),
+            multi_part_max_parallelism: TaskDefaultParameters::multi_part_max_parallelism_default(),
+            exclude: TaskDefaultParameters::filter_default(),
+            include: TaskDefaultParameters::filter_default(),
+            transfer_type: TaskDefaultParameters::transfer_type_default(),
+            last_modify_filter: TaskDefaultParameters::last_modify_filter_default(),
+        }
+    }
+}
+
+impl TransferTaskAttributes {
+    pub fn regex_filter(&self) -> Result<RegexFilter> {
+        RegexFilter::from_vec(&self.exclude, &self.include)
+    }
+
+    pub fn modify_filter(&self) -> Option<commons::LastModifyFilter> {
+        self.last_modify_filter.map(commons::LastModifyFilter::from)
+    }
+}
+
 pub fn de_usize_from_str<'de, D>(deserializer: D) -> Result<usize, D::Error>
 where
     D: Deserializer<'de>,
@@ -234,6 +314,58 @@ pub struct CompareTaskAttributes {
     #[serde(default = "TaskDefaultParameters::last_modify_filter_default")]
     pub last_modify_filter: Option<LastModifyFilter>,
 }
+
+impl Default for CompareTaskAttributes {
+    fn default() -> Self {
+        Self {
+            objects_per_batch: TaskDefaultParameters::objects_per_batch_default(),
+            task_parallelism: TaskDefaultParameters::task_parallelism_default(),
+            max_errors: TaskDefaultParameters::max_errors_default(),
+            meta_dir: TaskDefaultParameters::meta_dir_default(),
+            start_from_checkpoint: TaskDefaultParameters::start_from_checkpoint_default(),
+            large_file_size: TaskDefaultParameters::large_file_size_default(),
+            multi_part_chunk: TaskDefaultParameters::multi_part_chunk_size_default(),
+            exclude: TaskDefaultParameters::filter_default(),
+            include: TaskDefaultParameters::filter_default(),
+            exprirs_diff_scope: TaskDefaultParameters::exprirs_diff_scope_default(),
+            continuous: TaskDefaultParameters::continuous_default(),
+            last_modify_filter: TaskDefaultParameters::last_modify_filter_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TruncateTaskAttributes {
+    #[serde(default = "TaskDefaultParameters::objects_per_batch_default")]
+    pub objects_per_batch: i32,
+    #[serde(default = "TaskDefaultParameters::task_parallelism_default")]
+    pub task_parallelism: usize,
+    #[serde(default = "TaskDefaultParameters::max_errors_default")]
+    pub max_errors: usize,
+    #[serde(default = "TaskDefaultParameters::meta_dir_default")]
+    pub meta_dir: String,
+    #[serde(default = "TaskDefaultParameters::filter_default")]
+    pub exclude: Option<Vec<String>>,
+    #[serde(default = "TaskDefaultParameters::filter_default")]
+    pub include: Option<Vec<String>>,
+    #[serde(default = "TaskDefaultParameters::last_modify_filter_default")]
+    pub last_modify_filter: Option<LastModifyFilter>,
+}
+
+impl Default for TruncateTaskAttributes {
+    fn default() -> Self {
+        Self {
+            objects_per_batch: TaskDefaultParameters::objects_per_batch_default(),
+            task_parallelism: TaskDefaultParameters::task_parallelism_default(),
+            max_errors: TaskDefaultParameters::max_errors_default(),
+            meta_dir: TaskDefaultParameters::meta_dir_default(),
+            exclude: TaskDefaultParameters::filter_default(),
+            include: TaskDefaultParameters::filter_default(),
+            last_modify_filter: TaskDefaultParameters::last_modify_filter_default(),
+        }
+    }
+}
+
 pub struct TaskDefaultParameters {}
 
 impl TaskDefaultParameters {
@@ -280,8 +412,9 @@ impl TaskDefaultParameters {
     pub fn multi_part_chunks_per_batch_default() -> usize {
         10
     }
+    // 不超过最大并行度，单核时 cpu核数+2 大于 cpu核数*2
     pub fn multi_part_parallelism_default() -> usize {
-        num_cpus::get() + 2
+        (num_cpus::get() + 2).min(Self::multi_part_max_parallelism_default())
     }
 
     pub fn multi_part_max_parallelism_default() -> usize {
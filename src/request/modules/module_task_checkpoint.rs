@@ -0,0 +1,51 @@
+use super::TransferStage;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FilePosition {
+    pub offset: usize,
+    pub line_num: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FileDescription {
+    pub path: String,
+    pub size: u64,
+    pub total_lines: u64,
+}
+
+// 任务断点信息，用于判断任务是否可以从 checkpoint 继续执行
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskCheckpoint {
+    pub task_id: String,
+    // 当前执行的文件列表文件
+    pub executed_file: FileDescription,
+    // 已执行到的位置
+    pub executed_file_position: FilePosition,
+    // 增量阶段记录变更的文件
+    pub file_for_notify: Option<String>,
+    pub task_stage: TransferStage,
+    // checkpoint 最后修改时间
+    pub modify_checkpoint_timestamp: i128,
+    // 任务开始时间
+    pub task_begin_timestamp: i128,
+}
+
+impl TaskCheckpoint {
+    pub fn executed_percent(&self) -> f64 {
+        match self.executed_file.total_lines.eq(&0) {
+            true => 0.0,
+            false => {
+                self.executed_file_position.line_num as f64 / self.executed_file.total_lines as f64
+                    * 100.0
+            }
+        }
+    }
+
+    // 存量阶段已执行到文件列表末尾
+    pub fn is_stock_finished(&self) -> bool {
+        matches!(self.task_stage, TransferStage::Stock)
+            && self.executed_file.total_lines > 0
+            && self.executed_file_position.line_num >= self.executed_file.total_lines
+    }
+}
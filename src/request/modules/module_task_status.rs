@@ -83,6 +83,11 @@ impl TaskStatus {
         };
     }
 
+    // 任务处于启动中或运行中
+    pub fn is_living(&self) -> bool {
+        self.is_starting() || self.is_running()
+    }
+
     pub fn is_stopped(&self) -> bool {
         match &self.status {
             Status::Transfer(t) => match t {
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::request::modules::module_task::TaskDefaultParameters;
+
+use super::module_storage::OSSDescription;
+use super::module_task::TruncateTaskAttributes;
+
+// 清空 bucket 任务，删除 target 中 prefix 下符合过滤条件的全部对象
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub struct TruncateTask {
+    #[serde(default = "TaskDefaultParameters::id_default")]
+    pub task_id: String,
+    #[serde(default = "TaskDefaultParameters::name_default")]
+    pub name: String,
+    pub target: OSSDescription,
+    pub attributes: TruncateTaskAttributes,
+}
@@ -1,12 +1,16 @@
-use super::TaskServer;
+use super::{http_config, Response, ServerAuth, ServerTls, TaskServer};
 use crate::{
     configure::CurrentSettings,
-    resources::{get_current_settings, get_task_server_from_cf, save_current_settings},
+    exception::ClientError,
+    resources::{
+        find_task_server, get_current_settings, get_task_server_from_cf, save_current_settings,
+    },
 };
 use anyhow::Result;
 use once_cell::sync::Lazy;
+use reqwest::{RequestBuilder, Url};
+use serde::{de::DeserializeOwned, Serialize};
 use std::sync::{Arc, RwLock};
-use tokio::runtime;
 
 pub static GLOBAL_CURRENT_SERVER: Lazy<Arc<RwLock<TaskServer>>> = Lazy::new(|| {
     let task_server = match get_current_settings() {
@@ -21,8 +25,11 @@ pub static GLOBAL_CURRENT_SERVER: Lazy<Arc<RwLock<TaskServer>>> = Lazy::new(|| {
     t_s
 });
 
-pub fn set_current_server(server_id: &str) -> Result<String> {
-    let task_server = get_task_server_from_cf(server_id)?;
+pub const SERVER_ENV: &'static str = "FILES_PIPE_SERVER";
+
+// server 可为 id 或名称，持久化为当前 server
+pub fn set_current_server(server: &str) -> Result<String> {
+    let (server_id, task_server) = find_task_server(server)?;
     let mut current_server = GLOBAL_CURRENT_SERVER.write().unwrap();
     *current_server = task_server.clone();
     let mut current_settings = match get_current_settings() {
@@ -30,24 +37,133 @@ pub fn set_current_server(server_id: &str) -> Result<String> {
         Err(_) => CurrentSettings::default(),
     };
 
-    current_settings.current_server_id = server_id.to_string();
+    current_settings.current_server_id = server_id;
     let _ = save_current_settings(&current_settings)?;
 
     Ok(task_server.url)
 }
 
-pub fn test_reqwest() {
-    let rt = runtime::Builder::new_multi_thread()
-        .worker_threads(num_cpus::get())
-        .enable_all()
-        .max_io_events_per_tick(32)
-        .build()
-        .unwrap();
+// 解析 --server 参数，http(s) 开头按 url 处理，否则按 id 或名称查找已保存的 server
+pub fn resolve_task_server(server: &str) -> Result<TaskServer> {
+    if server.starts_with("http://") || server.starts_with("https://") {
+        server
+            .parse::<Url>()
+            .map_err(|_| ClientError::InvalidServerUrl(server.to_string()))?;
+        return Ok(TaskServer {
+            name: server.to_string(),
+            url: server.to_string(),
+            auth: ServerAuth::default(),
+            tls: ServerTls::default(),
+        });
+    }
+    let (_, task_server) = find_task_server(server)?;
+    Ok(task_server)
+}
 
-    let async_req = async {
-        let resp = reqwest::get("https://www.baidu.com").await.unwrap();
-        println!("{resp:#?}");
-    };
+// 单次调用覆盖当前 server，不修改持久化配置，drop 时恢复
+pub struct ServerOverride {
+    previous: TaskServer,
+}
+
+impl Drop for ServerOverride {
+    fn drop(&mut self) {
+        let mut current_server = GLOBAL_CURRENT_SERVER.write().unwrap();
+        *current_server = self.previous.clone();
+    }
+}
+
+pub fn override_current_server(server: &str) -> Result<ServerOverride> {
+    let task_server = resolve_task_server(server)?;
+    let mut current_server = GLOBAL_CURRENT_SERVER.write().unwrap();
+    let previous = std::mem::replace(&mut *current_server, task_server);
+    Ok(ServerOverride { previous })
+}
+
+// 拼接 server 的 api 地址
+pub fn api_url(server_url: &str, path: &str) -> Result<Url, ClientError> {
+    let mut url = server_url
+        .parse::<Url>()
+        .map_err(|_| ClientError::InvalidServerUrl(server_url.to_string()))?;
+    url.set_path(path);
+    Ok(url)
+}
+
+// 发送请求并解析响应，非 2xx 状态码转换为 HttpStatus 错误
+pub async fn send_request<T>(req: RequestBuilder) -> Result<Response<T>, ClientError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let resp = req.send().await?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(ClientError::HttpStatus {
+            status: status.as_u16(),
+            body,
+        });
+    }
+    let resp = resp.json::<Response<T>>().await?;
+    Ok(resp)
+}
+
+// 幂等请求遇到可重试错误时按配置指数退避重试，非幂等请求（create、start 等）不得使用
+pub async fn send_idempotent_request<T>(req: RequestBuilder) -> Result<Response<T>, ClientError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let retry = http_config().retry;
+    let mut attempt = 0;
+    loop {
+        // 请求体不可复制时无法重试
+        let r = match req.try_clone() {
+            Some(r) => r,
+            None => return send_request::<T>(req).await,
+        };
+        match send_request::<T>(r).await {
+            Err(e) if e.is_retryable() && attempt < retry.max_retries => {
+                let backoff = retry.backoff(attempt);
+                log::warn!("request failed: {}, retry after {:?}", e, backoff);
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            }
+            rs => return rs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{send_idempotent_request, send_request};
+    use crate::request::test_server::{ok_body, serve};
+    use std::net::TcpListener;
+    use tokio::runtime;
+
+    //cargo test request::req::test::test_send_idempotent_request -- --nocapture
+    #[test]
+    fn test_send_idempotent_request() {
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let client = reqwest::Client::new();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let mut statuses = vec![503, 502, 200].into_iter();
+        let server = serve(listener, 3, move |_| {
+            (statuses.next().unwrap(), ok_body(r#""done""#))
+        });
+        let rs = rt.block_on(async { send_idempotent_request::<String>(client.get(&url)).await });
+        assert_eq!(rs.unwrap().data, Some("done".to_string()));
+        server.join().unwrap();
 
-    rt.block_on(async_req);
+        // 非幂等请求不重试
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = serve(listener, 1, |_| (503, ok_body(r#""done""#)));
+        let rs = rt.block_on(async { send_request::<String>(client.post(&url)).await });
+        println!("{:?}", rs.as_ref().err());
+        assert!(rs.is_err());
+        server.join().unwrap();
+    }
 }
@@ -1,9 +1,10 @@
 use super::{
-    ReqTaskUpdate, RespListTask, Task, TaskId, TaskStatus, GLOBAL_CURRENT_SERVER,
-    GLOBAL_HTTP_CLIENT,
+    ApiClient, CompareStatus, Endpoint, ReqTaskUpdate, RespListTask, Status, Task, TaskCheckpoint,
+    TaskId, TaskStatus, TaskStopReason, TransferStage, TransferStatus,
 };
-use crate::request::modules::Response;
-use anyhow::Result;
+use crate::exception::ClientError;
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 pub const API_TASK_ALL: &'static str = "/api/v1/task/all";
 pub const API_TASK_SHOW: &'static str = "/api/v1/task/show";
@@ -18,163 +19,277 @@ pub const API_TASK_STATUS: &'static str = "/api/v1/task/status";
 pub const API_TASK_ANALYZE: &'static str = "/api/v1/task/analyze";
 pub const API_TASK_ALL_LIVING: &'static str = "/api/v1/task/all_living";
 
-pub async fn task_show(id: &TaskId) -> Result<Response<Task>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TASK_SHOW);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(id)
-        .send()
-        .await?
-        .json::<Response<Task>>()
-        .await?;
-    Ok(resp)
+// 任务相关 api，只读请求可重试
+pub const ENDPOINT_TASK_ALL: Endpoint<(), Vec<RespListTask>> =
+    Endpoint::post_idempotent(API_TASK_ALL);
+pub const ENDPOINT_TASK_SHOW: Endpoint<TaskId, Task> = Endpoint::post_idempotent(API_TASK_SHOW);
+pub const ENDPOINT_TASK_CREATE: Endpoint<Task, TaskId> = Endpoint::post(API_TASK_CREATE);
+pub const ENDPOINT_TASK_UPDATE: Endpoint<ReqTaskUpdate, ()> = Endpoint::post(API_TASK_UPDATE);
+pub const ENDPOINT_TASK_START: Endpoint<TaskId, ()> = Endpoint::post(API_TASK_START);
+pub const ENDPOINT_TASK_STOP: Endpoint<TaskId, Task> = Endpoint::post(API_TASK_STOP);
+pub const ENDPOINT_TASK_REMOVE: Endpoint<TaskId, ()> = Endpoint::post(API_TASK_REMOVE);
+pub const ENDPOINT_TASK_CLEAN: Endpoint<TaskId, ()> = Endpoint::post(API_TASK_CLEAN);
+pub const ENDPOINT_TASK_CHECKPOINT: Endpoint<TaskId, TaskCheckpoint> =
+    Endpoint::post_idempotent(API_TASK_CHECKPOINT);
+pub const ENDPOINT_TASK_STATUS: Endpoint<TaskId, TaskStatus> =
+    Endpoint::post_idempotent(API_TASK_STATUS);
+pub const ENDPOINT_TASK_ANALYZE: Endpoint<TaskId, BTreeMap<String, i128>> =
+    Endpoint::post_idempotent(API_TASK_ANALYZE);
+pub const ENDPOINT_TASK_ALL_LIVING: Endpoint<(), Vec<TaskStatus>> =
+    Endpoint::post_idempotent(API_TASK_ALL_LIVING);
+
+impl ApiClient {
+    pub async fn task_show(&self, id: &TaskId) -> Result<Task, ClientError> {
+        self.call(&ENDPOINT_TASK_SHOW, id).await?.into_data()
+    }
+
+    pub async fn task_create(&self, task: &Task) -> Result<TaskId, ClientError> {
+        self.call(&ENDPOINT_TASK_CREATE, task).await?.into_data()
+    }
+
+    pub async fn task_update(&self, task: &ReqTaskUpdate) -> Result<(), ClientError> {
+        self.call(&ENDPOINT_TASK_UPDATE, task)
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    pub async fn task_start(&self, id: &TaskId) -> Result<(), ClientError> {
+        self.call(&ENDPOINT_TASK_START, id).await?.into_result()?;
+        Ok(())
+    }
+
+    pub async fn task_stop(&self, id: &TaskId) -> Result<(), ClientError> {
+        self.call(&ENDPOINT_TASK_STOP, id).await?.into_result()?;
+        Ok(())
+    }
+
+    pub async fn task_clean(&self, id: &TaskId) -> Result<(), ClientError> {
+        self.call(&ENDPOINT_TASK_CLEAN, id).await?.into_result()?;
+        Ok(())
+    }
+
+    pub async fn task_remove(&self, id: &TaskId) -> Result<(), ClientError> {
+        self.call(&ENDPOINT_TASK_REMOVE, id).await?.into_result()?;
+        Ok(())
+    }
+
+    // 任务未运行时 server 不返回状态数据
+    pub async fn task_status(&self, id: &TaskId) -> Result<Option<TaskStatus>, ClientError> {
+        self.call(&ENDPOINT_TASK_STATUS, id).await?.into_result()
+    }
+
+    pub async fn task_checkpoint(&self, id: &TaskId) -> Result<TaskCheckpoint, ClientError> {
+        self.call(&ENDPOINT_TASK_CHECKPOINT, id).await?.into_data()
+    }
+
+    pub async fn task_analyze(&self, id: &TaskId) -> Result<BTreeMap<String, i128>, ClientError> {
+        self.call(&ENDPOINT_TASK_ANALYZE, id).await?.into_data()
+    }
+
+    // 启动中或运行中的任务状态，data 为空时视为空列表
+    pub async fn task_all_living(&self) -> Result<Vec<TaskStatus>, ClientError> {
+        let resp = self.call_empty(&ENDPOINT_TASK_ALL_LIVING).await?;
+        let status_list: Vec<TaskStatus> = resp.into_result()?.unwrap_or_default();
+        Ok(status_list.into_iter().filter(|s| s.is_living()).collect())
+    }
+
+    // data 为空时视为空列表
+    pub async fn list_all_tasks(&self) -> Result<Vec<RespListTask>, ClientError> {
+        let resp = self.call_empty(&ENDPOINT_TASK_ALL).await?;
+        Ok(resp.into_result()?.unwrap_or_default())
+    }
+
+    // 按 interval 轮询任务状态并回调 on_status，任务停止或 server 不再返回状态时结束，
+    // 返回最后一次获取到的状态。回调的第二个参数为执行进度百分比：存量及 compare 运行中
+    // 取自 checkpoint（暂不可用时为 None），增量阶段为 100，其余状态为 None
+    pub async fn task_watch<F>(
+        &self,
+        id: &TaskId,
+        interval: Duration,
+        mut on_status: F,
+    ) -> Result<Option<TaskStatus>, ClientError>
+    where
+        F: FnMut(&TaskStatus, Option<f64>),
+    {
+        let mut last = None;
+        loop {
+            let status = match self.task_status(id).await? {
+                Some(s) => s,
+                None => return Ok(last),
+            };
+            let percent = match &status.status {
+                Status::Transfer(TransferStatus::Running(TransferStage::Stock))
+                | Status::Compare(CompareStatus::Running) => self
+                    .task_checkpoint(id)
+                    .await
+                    .ok()
+                    .map(|c| c.executed_percent()),
+                Status::Transfer(TransferStatus::Running(TransferStage::Increment)) => Some(100.0),
+                _ => None,
+            };
+            on_status(&status, percent);
+            let stopped = status.is_stopped();
+            last = Some(status);
+            if stopped {
+                return Ok(last);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    // 根据最后一次获取到的状态确定任务停止原因。状态不含停止原因时（server 已不再返回状态，
+    // 或 compare 任务）查询 checkpoint，存量阶段已执行完视为正常结束，仍无法确定时返回 None
+    pub async fn task_stop_reason(
+        &self,
+        id: &TaskId,
+        last: &TaskStatus,
+    ) -> Result<Option<TaskStopReason>, ClientError> {
+        if let Status::Transfer(TransferStatus::Stopped(reason)) = &last.status {
+            return Ok(Some(reason.clone()));
+        }
+        match self.task_checkpoint(id).await {
+            Ok(checkpoint) => Ok(checkpoint
+                .is_stock_finished()
+                .then_some(TaskStopReason::Finish)),
+            // 任务已删除或不存在 checkpoint
+            Err(ClientError::Server { .. })
+            | Err(ClientError::MissingData)
+            | Err(ClientError::Decode(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// 以下函数访问当前 server
+pub async fn task_show(id: &TaskId) -> Result<Task, ClientError> {
+    ApiClient::current()?.task_show(id).await
+}
+
+pub async fn task_create(task: &Task) -> Result<TaskId, ClientError> {
+    ApiClient::current()?.task_create(task).await
+}
+
+pub async fn task_update(task: &ReqTaskUpdate) -> Result<(), ClientError> {
+    ApiClient::current()?.task_update(task).await
 }
 
-pub async fn task_create(task: &Task) -> Result<Response<TaskId>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TASK_CREATE);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(task)
-        .send()
-        .await?
-        .json::<Response<TaskId>>()
-        .await?;
-    Ok(resp)
+pub async fn task_start(id: &TaskId) -> Result<(), ClientError> {
+    ApiClient::current()?.task_start(id).await
 }
 
-pub async fn task_update(task: &ReqTaskUpdate) -> Result<Response<()>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TASK_UPDATE);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(task)
-        .send()
-        .await?
-        .json::<Response<()>>()
-        .await?;
-    Ok(resp)
+pub async fn task_stop(id: &TaskId) -> Result<(), ClientError> {
+    ApiClient::current()?.task_stop(id).await
 }
 
-pub async fn task_start(id: &TaskId) -> Result<Response<()>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(&API_TASK_START);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(id)
-        .send()
-        .await?
-        .json::<Response<()>>()
-        .await?;
-    Ok(resp)
+pub async fn task_clean(id: &TaskId) -> Result<(), ClientError> {
+    ApiClient::current()?.task_clean(id).await
 }
 
-pub async fn task_stop(id: &TaskId) -> Result<Response<Task>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(&API_TASK_STOP);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(id)
-        .send()
-        .await?
-        .json::<Response<Task>>()
-        .await?;
-    Ok(resp)
+pub async fn task_remove(id: &TaskId) -> Result<(), ClientError> {
+    ApiClient::current()?.task_remove(id).await
 }
 
-pub async fn task_clean(id: &TaskId) -> Result<Response<()>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TASK_CLEAN);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(id)
-        .send()
-        .await?
-        .json::<Response<()>>()
-        .await?;
-    Ok(resp)
+pub async fn task_status(id: &TaskId) -> Result<Option<TaskStatus>, ClientError> {
+    ApiClient::current()?.task_status(id).await
 }
 
-pub async fn task_remove(id: &TaskId) -> Result<Response<()>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TASK_REMOVE);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(id)
-        .send()
-        .await?
-        .json::<Response<()>>()
-        .await?;
-    Ok(resp)
+pub async fn task_checkpoint(id: &TaskId) -> Result<TaskCheckpoint, ClientError> {
+    ApiClient::current()?.task_checkpoint(id).await
 }
 
-pub async fn task_status(id: &TaskId) -> Result<Response<TaskStatus>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TASK_STATUS);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .json(id)
-        .send()
-        .await?
-        .json::<Response<TaskStatus>>()
-        .await?;
-    Ok(resp)
+pub async fn task_analyze(id: &TaskId) -> Result<BTreeMap<String, i128>, ClientError> {
+    ApiClient::current()?.task_analyze(id).await
 }
 
-pub async fn list_all_tasks() -> Result<Response<Vec<RespListTask>>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TASK_ALL);
-
-    let resp = GLOBAL_HTTP_CLIENT
-        .post(url)
-        .send()
-        .await?
-        .json::<Response<Vec<RespListTask>>>()
-        .await?;
-    Ok(resp)
+pub async fn task_all_living() -> Result<Vec<TaskStatus>, ClientError> {
+    ApiClient::current()?.task_all_living().await
+}
+
+pub async fn list_all_tasks() -> Result<Vec<RespListTask>, ClientError> {
+    ApiClient::current()?.list_all_tasks().await
+}
+
+#[cfg(test)]
+mod test {
+    use crate::request::test_server::serve_data;
+    use crate::request::{ApiClient, TaskId, TaskServer, TaskStopReason};
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use tokio::runtime;
+
+    //cargo test request::req_task::test::test_task_watch -- --nocapture
+    #[test]
+    fn test_task_watch() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let task_server = TaskServer {
+            url: format!("http://{}", listener.local_addr().unwrap()),
+            ..Default::default()
+        };
+        let server = serve_data(
+            listener,
+            vec![
+                r#"{"task_id":"1","start_time":0,"status":{"Transfer":{"Running":"Stock"}}}"#,
+                r#"{"task_id":"1","executed_file":{"path":"/tmp/meta/1/objlist","size":100,"total_lines":10},"executed_file_position":{"offset":30,"line_num":3},"file_for_notify":null,"task_stage":"Stock","modify_checkpoint_timestamp":0,"task_begin_timestamp":0}"#,
+                r#"{"task_id":"1","start_time":0,"status":{"Transfer":{"Running":"Increment"}}}"#,
+                r#"{"task_id":"1","start_time":0,"status":{"Transfer":{"Stopped":"Finish"}}}"#,
+                // server 不再返回状态，checkpoint 显示存量已执行完；运行中 checkpoint 暂不可用
+                r#"{"task_id":"1","start_time":0,"status":{"Transfer":{"Running":"Stock"}}}"#,
+                "null",
+                "null",
+                r#"{"task_id":"1","executed_file":{"path":"/tmp/meta/1/objlist","size":100,"total_lines":10},"executed_file_position":{"offset":100,"line_num":10},"file_for_notify":null,"task_stage":"Stock","modify_checkpoint_timestamp":0,"task_begin_timestamp":0}"#,
+                // server 不再返回状态，存量未执行完
+                r#"{"task_id":"1","start_time":0,"status":{"Transfer":{"Running":"Stock"}}}"#,
+                "null",
+                "null",
+                r#"{"task_id":"1","executed_file":{"path":"/tmp/meta/1/objlist","size":100,"total_lines":10},"executed_file_position":{"offset":30,"line_num":3},"file_for_notify":null,"task_stage":"Stock","modify_checkpoint_timestamp":0,"task_begin_timestamp":0}"#,
+            ],
+        );
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let client = ApiClient::new(task_server).unwrap();
+        let id = TaskId {
+            task_id: "1".to_string(),
+        };
+        let mut stages = vec![];
+        let last = rt
+            .block_on(async {
+                client
+                    .task_watch(&id, Duration::from_millis(10), |s, percent| {
+                        stages.push((s.status.to_string(), percent))
+                    })
+                    .await
+            })
+            .unwrap();
+        assert_eq!(
+            stages,
+            vec![
+                ("Stock".to_string(), Some(30.0)),
+                ("Increment".to_string(), Some(100.0)),
+                ("Finish".to_string(), None)
+            ]
+        );
+        let last = last.unwrap();
+        assert!(last.is_stopped());
+
+        let reasons = rt.block_on(async {
+            let mut reasons = vec![client.task_stop_reason(&id, &last).await.unwrap()];
+            for _ in 0..2 {
+                let last = client
+                    .task_watch(&id, Duration::from_millis(10), |_, _| {})
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert!(last.is_running());
+                reasons.push(client.task_stop_reason(&id, &last).await.unwrap());
+            }
+            reasons
+        });
+        assert!(matches!(reasons[0], Some(TaskStopReason::Finish)));
+        assert!(matches!(reasons[1], Some(TaskStopReason::Finish)));
+        assert!(reasons[2].is_none());
+        server.join().unwrap();
+    }
 }
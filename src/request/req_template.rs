@@ -1,6 +1,5 @@
-use anyhow::Result;
-
-use super::{Response, Task, GLOBAL_CURRENT_SERVER, GLOBAL_HTTP_CLIENT};
+use super::{ApiClient, Endpoint, Task};
+use crate::exception::ClientError;
 
 pub const API_TEMPLATE_TRANSFER_OSS2OSS: &'static str = "/api/v1/task/template/transfer/oss2oss";
 pub const API_TEMPLATE_TRANSFER_LOCAL2OSS: &'static str =
@@ -9,71 +8,60 @@ pub const API_TEMPLATE_TRANSFER_OSS2LOCAL: &'static str =
     "/api/v1/task/template/transfer/oss2local";
 pub const API_TEMPLATE_TRANSFER_LOCAL2LOCAL: &'static str =
     "/api/v1/task/template/transfer/local2local";
+pub const API_TEMPLATE_COMPARE: &'static str = "/api/v1/task/template/compare";
+pub const API_TEMPLATE_TRUNCATE_BUCKET: &'static str = "/api/v1/task/template/truncate_bucket";
 
-pub async fn template_transfer_oss2oss() -> Result<Response<Task>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TEMPLATE_TRANSFER_OSS2OSS);
+// 模板 api，均为只读 get 请求
+pub const ENDPOINT_TEMPLATE_TRANSFER_OSS2OSS: Endpoint<(), Task> =
+    Endpoint::get(API_TEMPLATE_TRANSFER_OSS2OSS);
+pub const ENDPOINT_TEMPLATE_TRANSFER_LOCAL2OSS: Endpoint<(), Task> =
+    Endpoint::get(API_TEMPLATE_TRANSFER_LOCAL2OSS);
+pub const ENDPOINT_TEMPLATE_TRANSFER_OSS2LOCAL: Endpoint<(), Task> =
+    Endpoint::get(API_TEMPLATE_TRANSFER_OSS2LOCAL);
+pub const ENDPOINT_TEMPLATE_TRANSFER_LOCAL2LOCAL: Endpoint<(), Task> =
+    Endpoint::get(API_TEMPLATE_TRANSFER_LOCAL2LOCAL);
+pub const ENDPOINT_TEMPLATE_COMPARE: Endpoint<(), Task> = Endpoint::get(API_TEMPLATE_COMPARE);
+pub const ENDPOINT_TEMPLATE_TRUNCATE_BUCKET: Endpoint<(), Task> =
+    Endpoint::get(API_TEMPLATE_TRUNCATE_BUCKET);
 
-    let resp = GLOBAL_HTTP_CLIENT
-        .get(url)
-        .send()
-        .await?
-        .json::<Response<Task>>()
-        .await?;
-    Ok(resp)
+impl ApiClient {
+    pub async fn template(&self, endpoint: &Endpoint<(), Task>) -> Result<Task, ClientError> {
+        self.call_empty(endpoint).await?.into_data()
+    }
 }
 
-pub async fn template_transfer_local2oss() -> Result<Response<Task>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TEMPLATE_TRANSFER_LOCAL2OSS);
+pub async fn template_transfer_oss2oss() -> Result<Task, ClientError> {
+    ApiClient::current()?
+        .template(&ENDPOINT_TEMPLATE_TRANSFER_OSS2OSS)
+        .await
+}
 
-    let resp = GLOBAL_HTTP_CLIENT
-        .get(url)
-        .send()
-        .await?
-        .json::<Response<Task>>()
-        .await?;
-    Ok(resp)
+pub async fn template_transfer_local2oss() -> Result<Task, ClientError> {
+    ApiClient::current()?
+        .template(&ENDPOINT_TEMPLATE_TRANSFER_LOCAL2OSS)
+        .await
 }
 
-pub async fn template_transfer_oss2local() -> Result<Response<Task>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TEMPLATE_TRANSFER_OSS2LOCAL);
+pub async fn template_transfer_oss2local() -> Result<Task, ClientError> {
+    ApiClient::current()?
+        .template(&ENDPOINT_TEMPLATE_TRANSFER_OSS2LOCAL)
+        .await
+}
 
-    let resp = GLOBAL_HTTP_CLIENT
-        .get(url)
-        .send()
-        .await?
-        .json::<Response<Task>>()
-        .await?;
-    Ok(resp)
+pub async fn template_transfer_local2local() -> Result<Task, ClientError> {
+    ApiClient::current()?
+        .template(&ENDPOINT_TEMPLATE_TRANSFER_LOCAL2LOCAL)
+        .await
 }
 
-pub async fn template_transfer_local2local() -> Result<Response<Task>> {
-    let mut url = GLOBAL_CURRENT_SERVER
-        .read()
-        .unwrap()
-        .url
-        .parse::<reqwest::Url>()?;
-    url.set_path(API_TEMPLATE_TRANSFER_LOCAL2LOCAL);
+pub async fn template_compare() -> Result<Task, ClientError> {
+    ApiClient::current()?
+        .template(&ENDPOINT_TEMPLATE_COMPARE)
+        .await
+}
 
-    let resp = GLOBAL_HTTP_CLIENT
-        .get(url)
-        .send()
-        .await?
-        .json::<Response<Task>>()
-        .await?;
-    Ok(resp)
+pub async fn template_truncate_bucket() -> Result<Task, ClientError> {
+    ApiClient::current()?
+        .template(&ENDPOINT_TEMPLATE_TRUNCATE_BUCKET)
+        .await
 }
@@ -0,0 +1,162 @@
+use anyhow::{anyhow, Result};
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    RequestBuilder,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: Option<String>,
+}
+
+// server 网关认证信息，bearer 与 basic 互斥，headers 附加到每个请求
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ServerAuth {
+    pub bearer_token: Option<String>,
+    pub basic: Option<BasicAuth>,
+    pub headers: Vec<(String, String)>,
+}
+
+impl ServerAuth {
+    // 解析 tui 输入，credential 格式为 'bearer <token>' 或 'basic <user>:<password>'，
+    // headers 格式为 'Name: value'，多个 header 以 ';' 分隔
+    pub fn from_spec(credential: &str, headers: &str) -> Result<Self> {
+        let mut auth = Self::default();
+        let credential = credential.trim();
+        if !credential.is_empty() {
+            match credential.split_once(char::is_whitespace) {
+                Some((kind, value)) if kind.eq_ignore_ascii_case("bearer") => {
+                    auth.bearer_token = Some(value.trim().to_string())
+                }
+                Some((kind, value)) if kind.eq_ignore_ascii_case("basic") => {
+                    auth.basic = Some(parse_basic(value.trim()))
+                }
+                _ => {
+                    return Err(anyhow!(
+                        "invalid auth {}, expect 'bearer <token>' or 'basic <user>:<password>'",
+                        credential
+                    ))
+                }
+            }
+        }
+
+        for header in headers.split(';').filter(|h| !h.trim().is_empty()) {
+            auth.headers.push(parse_header(header)?);
+        }
+        auth.validate()?;
+        Ok(auth)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.bearer_token.is_some() && self.basic.is_some() {
+            return Err(anyhow!(
+                "bearer token and basic auth are mutually exclusive"
+            ));
+        }
+        for (name, value) in self.headers.iter() {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| anyhow!("invalid header name {}", name))?;
+            HeaderValue::from_str(value)
+                .map_err(|_| anyhow!("invalid value of header {}", name))?;
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bearer_token.is_none() && self.basic.is_none() && self.headers.is_empty()
+    }
+
+    // 为请求附加认证信息
+    pub fn apply(&self, mut req: RequestBuilder) -> RequestBuilder {
+        if let Some(token) = &self.bearer_token {
+            req = req.bearer_auth(token);
+        }
+        if let Some(basic) = &self.basic {
+            req = req.basic_auth(&basic.username, basic.password.as_ref());
+        }
+        for (name, value) in self.headers.iter() {
+            req = req.header(name, value);
+        }
+        req
+    }
+
+    // 认证方式概要，不包含敏感信息，用于展示
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "none".to_string();
+        }
+        let mut items = vec![];
+        if self.bearer_token.is_some() {
+            items.push("bearer".to_string());
+        }
+        if let Some(basic) = &self.basic {
+            items.push(format!("basic:{}", basic.username));
+        }
+        if !self.headers.is_empty() {
+            let names = self
+                .headers
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<&str>>();
+            items.push(format!("headers:{}", names.join(",")));
+        }
+        items.join(" ")
+    }
+}
+
+// 'user:password' 或 'user'
+pub fn parse_basic(s: &str) -> BasicAuth {
+    match s.split_once(':') {
+        Some((username, password)) => BasicAuth {
+            username: username.to_string(),
+            password: Some(password.to_string()),
+        },
+        None => BasicAuth {
+            username: s.to_string(),
+            password: None,
+        },
+    }
+}
+
+// 'Name: value'
+pub fn parse_header(s: &str) -> Result<(String, String)> {
+    match s.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(anyhow!("invalid header {}, expect 'Name: value'", s.trim())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ServerAuth;
+
+    //cargo test request::server_auth::test::test_server_auth_from_spec -- --nocapture
+    #[test]
+    fn test_server_auth_from_spec() {
+        let auth = ServerAuth::from_spec("basic admin:p:w", "X-Tenant: t1; X-Env:dev").unwrap();
+        println!("{:?}", auth);
+        assert_eq!(auth.basic.as_ref().unwrap().username, "admin");
+        assert_eq!(
+            auth.basic.as_ref().unwrap().password.as_deref(),
+            Some("p:w")
+        );
+        assert_eq!(
+            auth.headers,
+            vec![
+                ("X-Tenant".to_string(), "t1".to_string()),
+                ("X-Env".to_string(), "dev".to_string())
+            ]
+        );
+        assert_eq!(auth.summary(), "basic:admin headers:X-Tenant,X-Env");
+
+        let auth = ServerAuth::from_spec("Bearer abc", "").unwrap();
+        assert_eq!(auth.bearer_token.as_deref(), Some("abc"));
+        assert!(ServerAuth::from_spec("", "").unwrap().is_empty());
+        assert!(ServerAuth::from_spec("token abc", "").is_err());
+        assert!(ServerAuth::from_spec("", "bad header: v").is_err());
+    }
+}
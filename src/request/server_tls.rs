@@ -0,0 +1,274 @@
+use crate::{configure::HttpConfig, exception::ClientError};
+use reqwest::{Certificate, Client, Identity};
+use serde::{Deserialize, Serialize};
+use std::{fs, time::Duration};
+
+// server tls 配置，证书及私钥均为 pem 文件路径，私钥需为 PKCS#8 格式
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ServerTls {
+    pub ca_cert: Option<String>,
+    pub client_cert: Option<String>,
+    pub client_key: Option<String>,
+    // 跳过证书校验，仅用于测试环境
+    pub insecure: bool,
+}
+
+impl ServerTls {
+    pub fn validate(&self) -> Result<(), ClientError> {
+        if self.client_cert.is_some() != self.client_key.is_some() {
+            return Err(ClientError::TlsConfig(
+                "client cert and client key must be set together".to_string(),
+            ));
+        }
+        self.build_client(&HttpConfig::default()).map(|_| ())
+    }
+
+    // 将证书及私钥路径转换为绝对路径，保存后可在任意工作目录下使用
+    pub fn canonicalize(&self) -> Result<ServerTls, ClientError> {
+        let abs = |path: &Option<String>| -> Result<Option<String>, ClientError> {
+            path.as_ref()
+                .map(|p| {
+                    fs::canonicalize(p)
+                        .map(|abs| abs.to_string_lossy().to_string())
+                        .map_err(|e| ClientError::TlsConfig(format!("{}: {}", p, e)))
+                })
+                .transpose()
+        };
+        Ok(ServerTls {
+            ca_cert: abs(&self.ca_cert)?,
+            client_cert: abs(&self.client_cert)?,
+            client_key: abs(&self.client_key)?,
+            insecure: self.insecure,
+        })
+    }
+
+    // 根据 tls 及超时配置创建 http client
+    pub fn build_client(&self, http: &HttpConfig) -> Result<Client, ClientError> {
+        let mut builder = Client::builder()
+            .connect_timeout(Duration::from_secs(http.connect_timeout_secs))
+            .timeout(Duration::from_secs(http.request_timeout_secs));
+
+        if let Some(ca) = &self.ca_cert {
+            let pem = read_pem(ca)?;
+            let certs = Certificate::from_pem_bundle(&pem)
+                .map_err(|e| ClientError::TlsConfig(format!("{}: {}", ca, e)))?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if let (Some(cert), Some(key)) = (&self.client_cert, &self.client_key) {
+            let identity = Identity::from_pkcs8_pem(&read_pem(cert)?, &read_pem(key)?)
+                .map_err(|e| ClientError::TlsConfig(format!("{}: {}", cert, e)))?;
+            builder = builder.identity(identity);
+        }
+
+        if self.insecure {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        builder
+            .build()
+            .map_err(|e| ClientError::TlsConfig(e.to_string()))
+    }
+
+    pub fn summary(&self) -> String {
+        let mut items = vec![];
+        if let Some(ca) = &self.ca_cert {
+            items.push(format!("ca:{}", ca));
+        }
+        if let Some(cert) = &self.client_cert {
+            items.push(format!("cert:{}", cert));
+        }
+        if self.insecure {
+            items.push("insecure".to_string());
+        }
+        match items.is_empty() {
+            true => "default".to_string(),
+            false => items.join(" "),
+        }
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>, ClientError> {
+    fs::read(path).map_err(|e| ClientError::TlsConfig(format!("{}: {}", path, e)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::ServerTls;
+    use crate::configure::HttpConfig;
+    use crate::request::test_server::{ok_body, respond};
+    use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode};
+    use std::net::TcpListener;
+    use tokio::runtime;
+
+    const TLS_TESTDATA: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/tls");
+
+    fn testdata(file: &str) -> String {
+        format!("{}/{}", TLS_TESTDATA, file)
+    }
+
+    // 使用测试 CA 签发的证书启动本地 https server，成功应答 times 次请求后退出
+    // client_auth 为 true 时要求客户端提供由测试 CA 签发的证书
+    fn serve(
+        listener: TcpListener,
+        times: usize,
+        client_auth: bool,
+    ) -> std::thread::JoinHandle<()> {
+        let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        builder
+            .set_private_key_file(testdata("server.key"), SslFiletype::PEM)
+            .unwrap();
+        builder
+            .set_certificate_chain_file(testdata("server.pem"))
+            .unwrap();
+        if client_auth {
+            builder.set_ca_file(testdata("ca.pem")).unwrap();
+            builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        }
+        let acceptor = builder.build();
+        std::thread::spawn(move || {
+            let mut served = 0;
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                // 证书校验失败的连接握手出错，继续等待下一个连接
+                let mut tls_stream = match acceptor.accept(stream) {
+                    Ok(s) => s,
+                    Err(_) => continue,
+                };
+                if respond(&mut tls_stream, &mut |_| (200, ok_body("null"))).is_err() {
+                    continue;
+                }
+                let _ = tls_stream.shutdown();
+                served += 1;
+                if served.eq(&times) {
+                    break;
+                }
+            }
+        })
+    }
+
+    //cargo test request::server_tls::test::test_server_tls -- --nocapture
+    #[test]
+    fn test_server_tls() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "https://localhost:{}/",
+            listener.local_addr().unwrap().port()
+        );
+        let server = serve(listener, 2, false);
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        // 系统根证书无法校验自签名证书
+        let default_client = ServerTls::default()
+            .build_client(&HttpConfig::default())
+            .unwrap();
+        let rs = rt.block_on(async { default_client.get(&url).send().await });
+        println!("{:?}", rs);
+        assert!(rs.is_err());
+
+        let tls = ServerTls {
+            ca_cert: Some(testdata("ca.pem")),
+            client_cert: Some(testdata("client.pem")),
+            client_key: Some(testdata("client.key")),
+            insecure: false,
+        };
+        tls.validate().unwrap();
+        let rs = rt.block_on(async {
+            tls.build_client(&HttpConfig::default())
+                .unwrap()
+                .get(&url)
+                .send()
+                .await
+        });
+        assert_eq!(rs.unwrap().status().as_u16(), 200);
+
+        let tls = ServerTls {
+            insecure: true,
+            ..Default::default()
+        };
+        let rs = rt.block_on(async {
+            tls.build_client(&HttpConfig::default())
+                .unwrap()
+                .get(&url)
+                .send()
+                .await
+        });
+        assert_eq!(rs.unwrap().status().as_u16(), 200);
+        server.join().unwrap();
+
+        let tls = ServerTls {
+            ca_cert: Some(testdata("not_exists.pem")),
+            ..Default::default()
+        };
+        assert!(tls.validate().is_err());
+        let tls = ServerTls {
+            client_cert: Some(testdata("client.pem")),
+            ..Default::default()
+        };
+        assert!(tls.validate().is_err());
+    }
+
+    //cargo test request::server_tls::test::test_mutual_tls -- --nocapture
+    #[test]
+    fn test_mutual_tls() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "https://localhost:{}/",
+            listener.local_addr().unwrap().port()
+        );
+        let server = serve(listener, 1, true);
+        let rt = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let get = |tls: &ServerTls| {
+            let client = tls.build_client(&HttpConfig::default()).unwrap();
+            rt.block_on(async { client.get(&url).send().await })
+        };
+
+        // 未提供客户端证书时握手失败
+        let tls = ServerTls {
+            ca_cert: Some(testdata("ca.pem")),
+            ..Default::default()
+        };
+        let rs = get(&tls);
+        println!("{:?}", rs);
+        assert!(rs.is_err());
+
+        let tls = ServerTls {
+            ca_cert: Some(testdata("ca.pem")),
+            client_cert: Some(testdata("client.pem")),
+            client_key: Some(testdata("client.key")),
+            insecure: false,
+        };
+        assert_eq!(get(&tls).unwrap().status().as_u16(), 200);
+        server.join().unwrap();
+    }
+
+    //cargo test request::server_tls::test::test_canonicalize -- --nocapture
+    #[test]
+    fn test_canonicalize() {
+        let tls = ServerTls {
+            ca_cert: Some("testdata/tls/ca.pem".to_string()),
+            client_cert: Some("testdata/tls/../tls/client.pem".to_string()),
+            client_key: None,
+            insecure: true,
+        };
+        let abs = tls.canonicalize().unwrap();
+        assert_eq!(abs.ca_cert, Some(testdata("ca.pem")));
+        assert_eq!(abs.client_cert, Some(testdata("client.pem")));
+        assert_eq!(abs.client_key, None);
+        assert!(abs.insecure);
+
+        let tls = ServerTls {
+            ca_cert: Some("testdata/tls/not_exists.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(tls.canonicalize().is_err());
+    }
+}
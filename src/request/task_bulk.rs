@@ -0,0 +1,204 @@
+use super::{ApiClient, RespListTask, TaskId, TaskIds, TaskType};
+use crate::exception::ClientError;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+// 批量操作时同时发往 server 的最大请求数
+pub const BULK_PARALLELISM: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkOp {
+    Start,
+    Stop,
+    Remove,
+    Clean,
+}
+
+impl BulkOp {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BulkOp::Start => "start",
+            BulkOp::Stop => "stop",
+            BulkOp::Remove => "remove",
+            BulkOp::Clean => "clean",
+        }
+    }
+}
+
+// 批量操作的任务选择条件，ids 为空时从全部任务中按条件筛选，
+// 否则 name_regex 及 task_type 在 ids 范围内筛选
+#[derive(Debug, Clone, Default)]
+pub struct TaskSelector {
+    pub ids: Vec<String>,
+    pub all: bool,
+    pub name_regex: Option<String>,
+    pub task_type: Option<TaskType>,
+}
+
+impl TaskSelector {
+    pub fn parse_type(s: &str) -> Result<TaskType> {
+        match s.to_lowercase().as_str() {
+            "transfer" => Ok(TaskType::Transfer),
+            "compare" => Ok(TaskType::Compare),
+            "truncatebucket" => Ok(TaskType::TruncateBucket),
+            _ => Err(anyhow!("unsupported task type '{}'", s)),
+        }
+    }
+
+    // 仅指定任务 id 时无需获取任务列表
+    pub fn need_task_list(&self) -> bool {
+        self.all || self.name_regex.is_some() || self.task_type.is_some()
+    }
+
+    pub fn select(&self, tasks: &[RespListTask]) -> Result<TaskIds> {
+        if !self.need_task_list() {
+            return Ok(TaskIds {
+                task_ids: self.ids.clone(),
+            });
+        }
+
+        let regex = match &self.name_regex {
+            Some(r) => Some(Regex::new(r)?),
+            None => None,
+        };
+        let task_ids = tasks
+            .iter()
+            .map(|t| &t.task)
+            .filter(|t| self.ids.is_empty() || self.ids.contains(&t.task_id()))
+            .filter(|t| match &regex {
+                Some(r) => r.is_match(&t.task_name()),
+                None => true,
+            })
+            .filter(|t| match &self.task_type {
+                Some(ty) => ty.eq(&t.task_type()),
+                None => true,
+            })
+            .map(|t| t.task_id())
+            .collect::<Vec<String>>();
+        Ok(TaskIds { task_ids })
+    }
+}
+
+// 单个任务的批量操作结果
+#[derive(Debug, Serialize)]
+pub struct BulkResult {
+    pub task_id: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ApiClient {
+    pub async fn task_op(&self, op: BulkOp, id: &TaskId) -> Result<(), ClientError> {
+        match op {
+            BulkOp::Start => self.task_start(id).await,
+            BulkOp::Stop => self.task_stop(id).await,
+            BulkOp::Remove => self.task_remove(id).await,
+            BulkOp::Clean => self.task_clean(id).await,
+        }
+    }
+
+    // 并发执行批量操作，结果顺序与 ids 一致
+    pub async fn task_bulk(&self, op: BulkOp, ids: &TaskIds) -> Vec<BulkResult> {
+        let semaphore = Arc::new(Semaphore::new(BULK_PARALLELISM));
+        let mut set = JoinSet::new();
+        for (idx, task_id) in ids.task_ids.iter().enumerate() {
+            let client = self.clone();
+            let semaphore = semaphore.clone();
+            let id = TaskId {
+                task_id: task_id.clone(),
+            };
+            set.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                (idx, client.task_op(op, &id).await)
+            });
+        }
+
+        let mut results = ids
+            .task_ids
+            .iter()
+            .map(|id| BulkResult {
+                task_id: id.clone(),
+                ok: false,
+                error: Some("not executed".to_string()),
+            })
+            .collect::<Vec<BulkResult>>();
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((idx, Ok(()))) => {
+                    results[idx].ok = true;
+                    results[idx].error = None;
+                }
+                Ok((idx, Err(e))) => results[idx].error = Some(e.to_string()),
+                Err(e) => log::error!("{}", e),
+            }
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TaskSelector;
+    use crate::request::{OfflineTemplate, RespListTask, Task, TaskType};
+
+    fn list_task(template: OfflineTemplate, id: &str, name: &str) -> RespListTask {
+        let mut task = template.task();
+        match &mut task {
+            Task::Transfer(t) => (t.task_id, t.name) = (id.to_string(), name.to_string()),
+            Task::Compare(c) => (c.task_id, c.name) = (id.to_string(), name.to_string()),
+            Task::TruncateBucket(t) => (t.task_id, t.name) = (id.to_string(), name.to_string()),
+        }
+        RespListTask {
+            cf_id: id.to_string(),
+            task,
+        }
+    }
+
+    //cargo test request::task_bulk::test::test_task_selector -- --nocapture
+    #[test]
+    fn test_task_selector() {
+        let tasks = vec![
+            list_task(OfflineTemplate::TransferOss2Oss, "1", "migrate-a"),
+            list_task(OfflineTemplate::Compare, "2", "migrate-a-check"),
+            list_task(OfflineTemplate::TransferLocal2Oss, "3", "backup"),
+        ];
+
+        let explicit = TaskSelector {
+            ids: vec!["9".to_string(), "1".to_string()],
+            ..Default::default()
+        };
+        assert!(!explicit.need_task_list());
+        assert_eq!(explicit.select(&tasks).unwrap().task_ids, vec!["9", "1"]);
+
+        let all = TaskSelector {
+            all: true,
+            ..Default::default()
+        };
+        assert_eq!(all.select(&tasks).unwrap().task_ids, vec!["1", "2", "3"]);
+
+        let by_name = TaskSelector {
+            name_regex: Some("^migrate-".to_string()),
+            task_type: Some(TaskSelector::parse_type("transfer").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(by_name.select(&tasks).unwrap().task_ids, vec!["1"]);
+
+        let in_ids = TaskSelector {
+            ids: vec!["2".to_string(), "3".to_string()],
+            task_type: Some(TaskType::Compare),
+            ..Default::default()
+        };
+        assert_eq!(in_ids.select(&tasks).unwrap().task_ids, vec!["2"]);
+
+        let bad_regex = TaskSelector {
+            name_regex: Some("(".to_string()),
+            ..Default::default()
+        };
+        assert!(bad_regex.select(&tasks).is_err());
+        assert!(TaskSelector::parse_type("sync").is_err());
+    }
+}
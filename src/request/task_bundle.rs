@@ -0,0 +1,171 @@
+use super::{task_id_generator, Task};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 任务导出文件格式版本，格式不兼容变更时递增
+pub const TASK_BUNDLE_VERSION: u32 = 1;
+
+// 任务导出文件，用于备份及跨 server 迁移任务
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskBundle {
+    pub version: u32,
+    // 导出任务的 server url
+    pub server: String,
+    pub exported_at: u64,
+    pub tasks: Vec<Task>,
+}
+
+impl TaskBundle {
+    pub fn new(server: &str, tasks: Vec<Task>) -> Self {
+        let exported_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self {
+            version: TASK_BUNDLE_VERSION,
+            server: server.to_string(),
+            exported_at,
+            tasks,
+        }
+    }
+
+    // 解析 yaml 或 json 格式的导出文件
+    pub fn parse(content: &str) -> Result<Self> {
+        let bundle = serde_yaml::from_str::<Self>(content)?;
+        if bundle.version > TASK_BUNDLE_VERSION {
+            return Err(anyhow!(
+                "unsupported task bundle version {}, max supported version {}",
+                bundle.version,
+                TASK_BUNDLE_VERSION
+            ));
+        }
+        Ok(bundle)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    // 为全部任务生成新 id
+    pub new_ids: bool,
+    // 任务 meta_dir 改写为 <meta_dir>/<task_id>
+    pub meta_dir: Option<String>,
+}
+
+// 待导入的任务，conflict 为 true 时任务 id 已存在于目标 server
+#[derive(Debug, Clone)]
+pub struct ImportItem {
+    pub source_id: String,
+    pub task: Task,
+    pub conflict: bool,
+}
+
+// 根据目标 server 已有任务 id 处理 id 冲突并改写 meta_dir
+pub fn prepare_import(
+    tasks: Vec<Task>,
+    existing: &HashSet<String>,
+    opts: &ImportOptions,
+) -> Vec<ImportItem> {
+    let mut used = existing.clone();
+    let mut items = vec![];
+    for mut task in tasks {
+        let source_id = task.task_id();
+        let mut conflict = used.contains(&source_id);
+        if opts.new_ids {
+            let new_id = unique_task_id(&used);
+            // meta_dir 中包含原任务 id 时同步替换，避免与原任务共用元数据目录
+            let meta_dir = task.meta_dir();
+            if !source_id.is_empty() && meta_dir.contains(&source_id) {
+                task.set_meta_dir(&meta_dir.replace(&source_id, &new_id));
+            }
+            task.set_task_id(&new_id);
+            conflict = false;
+        }
+        if let Some(dir) = &opts.meta_dir {
+            task.set_meta_dir(&format!("{}/{}", dir.trim_end_matches('/'), task.task_id()));
+        }
+        used.insert(task.task_id());
+        items.push(ImportItem {
+            source_id,
+            task,
+            conflict,
+        });
+    }
+    items
+}
+
+// 同一毫秒内 task_id_generator 生成的 id 相同，重复时重新生成
+fn unique_task_id(used: &HashSet<String>) -> String {
+    loop {
+        let id = task_id_generator().to_string();
+        if !used.contains(&id) {
+            return id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{prepare_import, ImportOptions, TaskBundle, TASK_BUNDLE_VERSION};
+    use crate::commons::yamlutile::struct_to_yaml_string;
+    use crate::request::{OfflineTemplate, Task};
+    use std::collections::HashSet;
+
+    fn task(id: &str, meta_dir: &str) -> Task {
+        let mut task = OfflineTemplate::TransferOss2Oss.task();
+        task.set_task_id(id);
+        task.set_meta_dir(meta_dir);
+        task
+    }
+
+    //cargo test request::task_bundle::test::test_task_bundle -- --nocapture
+    #[test]
+    fn test_task_bundle() {
+        let bundle = TaskBundle::new("http://127.0.0.1:3000", vec![task("1", "/tmp/meta")]);
+        let yaml = struct_to_yaml_string(&bundle).unwrap();
+        println!("{}", yaml);
+        let parsed = TaskBundle::parse(&yaml).unwrap();
+        assert_eq!(parsed.version, TASK_BUNDLE_VERSION);
+        assert_eq!(parsed.tasks[0].task_id(), "1");
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert_eq!(TaskBundle::parse(&json).unwrap().tasks.len(), 1);
+
+        let mut future = bundle.clone();
+        future.version = TASK_BUNDLE_VERSION + 1;
+        let yaml = struct_to_yaml_string(&future).unwrap();
+        assert!(TaskBundle::parse(&yaml).is_err());
+    }
+
+    //cargo test request::task_bundle::test::test_prepare_import -- --nocapture
+    #[test]
+    fn test_prepare_import() {
+        let existing = HashSet::from(["1".to_string()]);
+        let tasks = vec![task("1", "/tmp/meta/1"), task("2", "/tmp/meta/2")];
+
+        let items = prepare_import(tasks.clone(), &existing, &ImportOptions::default());
+        assert!(items[0].conflict);
+        assert!(!items[1].conflict);
+        assert_eq!(items[1].task.task_id(), "2");
+
+        let opts = ImportOptions {
+            new_ids: true,
+            meta_dir: None,
+        };
+        let items = prepare_import(tasks.clone(), &existing, &opts);
+        assert!(items.iter().all(|i| !i.conflict));
+        let new_id = items[0].task.task_id();
+        assert_ne!(new_id, "1");
+        assert_ne!(new_id, items[1].task.task_id());
+        assert_eq!(items[0].source_id, "1");
+        assert_eq!(items[0].task.meta_dir(), format!("/tmp/meta/{}", new_id));
+
+        let opts = ImportOptions {
+            new_ids: false,
+            meta_dir: Some("/data/meta/".to_string()),
+        };
+        let items = prepare_import(tasks, &HashSet::new(), &opts);
+        assert_eq!(items[1].task.meta_dir(), "/data/meta/2");
+    }
+}
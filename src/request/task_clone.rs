@@ -0,0 +1,75 @@
+use super::{patch_task, task_id_generator, Task};
+use crate::commons::set_json_path;
+use anyhow::Result;
+use serde_json::Value;
+
+// 复制任务并按路径覆盖属性，生成新的 task_id 及 meta_dir；
+// meta_dir 包含原任务 id 时替换为新 id，否则在其下新建以新 id 命名的目录
+pub fn clone_task(task: &Task, name: Option<&str>, overrides: &[(String, String)]) -> Result<Task> {
+    let mut cloned = task.clone();
+    let source_id = task.task_id();
+    let new_id = task_id_generator().to_string();
+    let meta_dir = task.meta_dir();
+    let new_meta_dir = match !source_id.is_empty() && meta_dir.contains(&source_id) {
+        true => meta_dir.replace(&source_id, &new_id),
+        false => format!("{}/{}", meta_dir.trim_end_matches('/'), new_id),
+    };
+    cloned.set_task_id(&new_id);
+    cloned.set_meta_dir(&new_meta_dir);
+
+    // --name 按原文设置，--set name=... 可再覆盖
+    if let Some(name) = name {
+        let mut value = serde_json::to_value(&cloned)?;
+        set_json_path(&mut value, "name", Value::String(name.to_string()))?;
+        cloned = serde_json::from_value(value)?;
+    }
+    patch_task(&cloned, None, overrides)
+}
+
+#[cfg(test)]
+mod test {
+    use super::clone_task;
+    use crate::commons::parse_path_assignment;
+    use crate::request::{ObjectStorage, OfflineTemplate, Task};
+
+    //cargo test request::task_clone::test::test_clone_task -- --nocapture
+    #[test]
+    fn test_clone_task() {
+        let mut task = OfflineTemplate::TransferOss2Oss.task();
+        task.set_task_id("1");
+        task.set_meta_dir("/tmp/meta_dir");
+        let overrides = vec![
+            parse_path_assignment("attributes.task_parallelism=32").unwrap(),
+            parse_path_assignment("target.prefix=new/").unwrap(),
+        ];
+
+        let cloned = clone_task(&task, Some("copy"), &overrides).unwrap();
+        println!("{:?}", cloned);
+        assert_ne!(cloned.task_id(), "1");
+        assert_eq!(cloned.task_name(), "copy");
+        assert_eq!(
+            cloned.meta_dir(),
+            format!("/tmp/meta_dir/{}", cloned.task_id())
+        );
+        match &cloned {
+            Task::Transfer(t) => {
+                assert_eq!(t.attributes.task_parallelism, 32);
+                match &t.target {
+                    ObjectStorage::OSS(oss) => assert_eq!(oss.prefix.as_deref(), Some("new/")),
+                    _ => panic!("target should be oss"),
+                }
+            }
+            _ => panic!("cloned task should be transfer"),
+        }
+
+        task.set_meta_dir("/data/meta/1");
+        let cloned = clone_task(&task, None, &[]).unwrap();
+        assert_eq!(
+            cloned.meta_dir(),
+            format!("/data/meta/{}", cloned.task_id())
+        );
+
+        let typo = vec![parse_path_assignment("attributes.task_paralelism=32").unwrap()];
+        assert!(clone_task(&task, None, &typo).is_err());
+    }
+}
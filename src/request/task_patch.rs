@@ -0,0 +1,102 @@
+use super::Task;
+use crate::commons::{
+    assignment_value, diff_lines, get_json_path, json_leaf_paths, merge_patch, set_json_path,
+    yamlutile::struct_to_yaml_string, DiffLine,
+};
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+// 修改任务描述，先合并 RFC 7396 merge patch，再按路径覆盖属性，覆盖值按已有属性类型转换
+pub fn patch_task(task: &Task, patch: Option<&Value>, sets: &[(String, String)]) -> Result<Task> {
+    let mut value = serde_json::to_value(task)?;
+    let mut paths = vec![];
+    if let Some(p) = patch {
+        merge_patch(&mut value, p);
+        paths.extend(json_leaf_paths(p));
+    }
+    for (path, raw) in sets {
+        let v = assignment_value(get_json_path(&value, path), raw)
+            .map_err(|e| anyhow!("{}: {}", path, e))?;
+        set_json_path(&mut value, path, v.clone())?;
+        if !v.is_null() {
+            paths.push(path.clone());
+        }
+    }
+    let patched = serde_json::from_value::<Task>(value)?;
+
+    // 未知属性在反序列化时被忽略，检查修改项均已生效以发现拼写错误
+    let check = serde_json::to_value(&patched)?;
+    for path in paths {
+        if get_json_path(&check, &path).is_none() {
+            return Err(anyhow!("unknown task property {}", path));
+        }
+    }
+    Ok(patched)
+}
+
+// 按 yaml 格式逐行比较新旧任务描述
+pub fn task_diff(old: &Task, new: &Task) -> Result<Vec<DiffLine>> {
+    Ok(diff_lines(
+        &struct_to_yaml_string(old)?,
+        &struct_to_yaml_string(new)?,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{patch_task, task_diff};
+    use crate::commons::{parse_path_assignment, DiffLine};
+    use crate::request::{ObjectStorage, OfflineTemplate, Task};
+    use serde_json::json;
+
+    //cargo test request::task_patch::test::test_patch_task -- --nocapture
+    #[test]
+    fn test_patch_task() {
+        let task = OfflineTemplate::TransferOss2Oss.task();
+        let patch = json!({"attributes": {"task_parallelism": 16, "exclude": ["\\.tmp$"]}, "target": {"prefix": null}});
+        let sets = vec![parse_path_assignment("name=patched").unwrap()];
+        let patched = patch_task(&task, Some(&patch), &sets).unwrap();
+        assert_eq!(patched.task_name(), "patched");
+        assert_eq!(patched.task_id(), task.task_id());
+        match &patched {
+            Task::Transfer(t) => {
+                assert_eq!(t.attributes.task_parallelism, 16);
+                assert_eq!(t.attributes.exclude, Some(vec!["\\.tmp$".to_string()]));
+            }
+            _ => panic!("patched task should be transfer"),
+        }
+
+        let diff = task_diff(&task, &patched).unwrap();
+        let changed = diff
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Same(_)))
+            .collect::<Vec<&DiffLine>>();
+        println!("{:?}", changed);
+        assert!(changed.contains(&&DiffLine::Added("name: patched".to_string())));
+        assert!(changed.contains(&&DiffLine::Removed("  prefix: test/samples/".to_string())));
+
+        let typo = json!({"attributes": {"task_paralelism": 16}});
+        assert!(patch_task(&task, Some(&typo), &[]).is_err());
+
+        // 数字形式的值写入字符串属性时保持字符串
+        let sets = vec![
+            parse_path_assignment("name=2024").unwrap(),
+            parse_path_assignment("target.bucket=123").unwrap(),
+            parse_path_assignment("target.prefix=null").unwrap(),
+        ];
+        let patched = patch_task(&task, None, &sets).unwrap();
+        assert_eq!(patched.task_name(), "2024");
+        match &patched {
+            Task::Transfer(t) => match &t.target {
+                ObjectStorage::OSS(oss) => {
+                    assert_eq!(oss.bucket, "123");
+                    assert_eq!(oss.prefix, None);
+                }
+                _ => panic!("target should be oss"),
+            },
+            _ => panic!("patched task should be transfer"),
+        }
+        let bad = vec![parse_path_assignment("attributes.task_parallelism=many").unwrap()];
+        assert!(patch_task(&task, None, &bad).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use super::Task;
+use crate::resources::get_credential_profile;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{env, fmt, fs};
+
+// 展示时替代凭据的掩码
+pub const SECRET_MASK: &'static str = "******";
+
+// 本地保存的对象存储访问凭据
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CredentialProfile {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl fmt::Debug for CredentialProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialProfile")
+            .field("access_key_id", &mask_access_key_id(&self.access_key_id))
+            .field("secret_access_key", &mask_secret(&self.secret_access_key))
+            .finish()
+    }
+}
+
+// 任务描述中的凭据引用，格式为 env:NAME、file:/path 或 profile:NAME
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    Env(String),
+    File(String),
+    Profile(String),
+}
+
+impl SecretRef {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (kind, name) = value.split_once(':')?;
+        if name.is_empty() {
+            return None;
+        }
+        match kind {
+            "env" => Some(SecretRef::Env(name.to_string())),
+            "file" => Some(SecretRef::File(name.to_string())),
+            "profile" => Some(SecretRef::Profile(name.to_string())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialField {
+    AccessKeyId,
+    SecretAccessKey,
+}
+
+impl CredentialField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CredentialField::AccessKeyId => "access_key_id",
+            CredentialField::SecretAccessKey => "secret_access_key",
+        }
+    }
+}
+
+// 解析凭据引用，非引用值原样返回
+pub fn resolve_secret(value: &str, field: CredentialField) -> Result<String> {
+    match SecretRef::parse(value) {
+        None => Ok(value.to_string()),
+        Some(SecretRef::Env(name)) => env::var(&name).map_err(|_| anyhow!("env {} not set", name)),
+        Some(SecretRef::File(path)) => fs::read_to_string(&path)
+            .map(|s| s.trim_end_matches(['\r', '\n']).to_string())
+            .map_err(|e| anyhow!("{}: {}", path, e)),
+        Some(SecretRef::Profile(name)) => {
+            let profile = get_credential_profile(&name)?;
+            Ok(match field {
+                CredentialField::AccessKeyId => profile.access_key_id,
+                CredentialField::SecretAccessKey => profile.secret_access_key,
+            })
+        }
+    }
+}
+
+// 提交 server 前将任务中的凭据引用替换为实际值
+pub fn resolve_task_secrets(task: &Task) -> Result<Task> {
+    let mut resolved = task.clone();
+    for oss in resolved.oss_descriptions_mut() {
+        for (field, value) in [
+            (CredentialField::AccessKeyId, &mut oss.access_key_id),
+            (CredentialField::SecretAccessKey, &mut oss.secret_access_key),
+        ] {
+            *value = resolve_secret(value, field)
+                .map_err(|e| anyhow!("{} of bucket {}: {}", field.name(), oss.bucket, e))?;
+        }
+    }
+    Ok(resolved)
+}
+
+// 凭据引用不含敏感信息，原样展示
+pub fn mask_secret(value: &str) -> String {
+    if value.is_empty() || SecretRef::parse(value).is_some() {
+        return value.to_string();
+    }
+    SECRET_MASK.to_string()
+}
+
+// access key id 保留前 4 位便于区分
+pub fn mask_access_key_id(value: &str) -> String {
+    if SecretRef::parse(value).is_some() || value.chars().count() <= 8 {
+        return mask_secret(value);
+    }
+    format!(
+        "{}{}",
+        value.chars().take(4).collect::<String>(),
+        SECRET_MASK
+    )
+}
+
+pub fn mask_task_secrets(task: &Task) -> Task {
+    let mut masked = task.clone();
+    for oss in masked.oss_descriptions_mut() {
+        oss.access_key_id = mask_access_key_id(&oss.access_key_id);
+        oss.secret_access_key = mask_secret(&oss.secret_access_key);
+    }
+    masked
+}
+
+pub fn has_masked_secrets(task: &Task) -> bool {
+    task.oss_descriptions().iter().any(|oss| {
+        oss.access_key_id.ends_with(SECRET_MASK) || oss.secret_access_key.eq(SECRET_MASK)
+    })
+}
+
+// 任务中存在未使用引用的明文凭据
+pub fn has_plain_secrets(task: &Task) -> bool {
+    task.oss_descriptions().iter().any(|oss| {
+        !mask_secret(&oss.access_key_id).eq(&oss.access_key_id)
+            || !mask_secret(&oss.secret_access_key).eq(&oss.secret_access_key)
+    })
+}
+
+// 编辑掩码后的任务时，未修改的凭据恢复为原值
+pub fn unmask_task_secrets(task: &mut Task, original: &Task) {
+    for (oss, orig) in task
+        .oss_descriptions_mut()
+        .into_iter()
+        .zip(original.oss_descriptions())
+    {
+        if oss
+            .access_key_id
+            .eq(&mask_access_key_id(&orig.access_key_id))
+        {
+            oss.access_key_id = orig.access_key_id.clone();
+        }
+        if oss
+            .secret_access_key
+            .eq(&mask_secret(&orig.secret_access_key))
+        {
+            oss.secret_access_key = orig.secret_access_key.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        has_masked_secrets, has_plain_secrets, mask_task_secrets, resolve_task_secrets,
+        unmask_task_secrets, SecretRef, SECRET_MASK,
+    };
+    use crate::request::{OfflineTemplate, Task};
+
+    fn set_credentials(task: &mut Task, access_key_id: &str, secret_access_key: &str) {
+        for oss in task.oss_descriptions_mut() {
+            oss.access_key_id = access_key_id.to_string();
+            oss.secret_access_key = secret_access_key.to_string();
+        }
+    }
+
+    //cargo test request::task_secret::test::test_task_secrets -- --nocapture
+    #[test]
+    fn test_task_secrets() {
+        assert_eq!(
+            SecretRef::parse("env:AK"),
+            Some(SecretRef::Env("AK".to_string()))
+        );
+        assert_eq!(SecretRef::parse("plain:value"), None);
+        assert_eq!(SecretRef::parse("env:"), None);
+
+        let secret_file =
+            std::env::temp_dir().join(format!("files_pipe_cli_secret_{}", std::process::id()));
+        std::fs::write(&secret_file, "file-secret\n").unwrap();
+        std::env::set_var("FILES_PIPE_CLI_TEST_AK", "AKIDFROMENV");
+
+        let mut task = OfflineTemplate::TransferOss2Oss.task();
+        set_credentials(
+            &mut task,
+            "env:FILES_PIPE_CLI_TEST_AK",
+            &format!("file:{}", secret_file.display()),
+        );
+        assert!(!has_plain_secrets(&task));
+        let resolved = resolve_task_secrets(&task).unwrap();
+        std::fs::remove_file(&secret_file).unwrap();
+        assert!(has_plain_secrets(&resolved));
+        for oss in resolved.oss_descriptions() {
+            assert_eq!(oss.access_key_id, "AKIDFROMENV");
+            assert_eq!(oss.secret_access_key, "file-secret");
+        }
+        assert_eq!(resolved.oss_descriptions().len(), 2);
+
+        // 引用原样展示，实际凭据被隐藏
+        let masked = mask_task_secrets(&task);
+        assert_eq!(
+            masked.oss_descriptions()[0].access_key_id,
+            "env:FILES_PIPE_CLI_TEST_AK"
+        );
+        let masked = mask_task_secrets(&resolved);
+        println!("{:?}", masked);
+        assert_eq!(masked.oss_descriptions()[0].access_key_id, "AKID******");
+        assert_eq!(masked.oss_descriptions()[0].secret_access_key, SECRET_MASK);
+        assert!(!format!("{:?}", resolved).contains("file-secret"));
+
+        assert!(has_masked_secrets(&masked));
+        assert!(!has_masked_secrets(&resolved));
+        let mut edited = masked.clone();
+        edited.oss_descriptions_mut()[1].secret_access_key = "new-secret".to_string();
+        unmask_task_secrets(&mut edited, &resolved);
+        assert_eq!(edited.oss_descriptions()[0].access_key_id, "AKIDFROMENV");
+        assert_eq!(
+            edited.oss_descriptions()[0].secret_access_key,
+            "file-secret"
+        );
+        assert_eq!(edited.oss_descriptions()[1].secret_access_key, "new-secret");
+
+        set_credentials(&mut task, "env:FILES_PIPE_CLI_TEST_NOT_SET", "x");
+        assert!(resolve_task_secrets(&task).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use super::{
+    CompareCheckOption, CompareTask, CompareTaskAttributes, OSSDescription, ObjectStorage, Task,
+    TaskDefaultParameters, TransferTask, TransferTaskAttributes, TruncateTask,
+    TruncateTaskAttributes,
+};
+use crate::commons::yamlutile::yaml_with_comments;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+// 任务描述 yaml 中各属性的说明，与 docs/task_yaml_reference.md 保持一致
+pub static TASK_YAML_COMMENTS: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
+    HashMap::from([
+        ("type", "任务类型：transfer、compare、truncatebucket"),
+        ("task_id", "任务id，自动生成，非必填"),
+        ("name", "任务名称，非必填"),
+        ("source", "源存储，支持 本地目录或oss bucket"),
+        ("target", "目标存储，支持 本地目录或oss bucket"),
+        ("provider", "oss 提供方:JD,JRSS,ALI,AWS,HUAWEI,COS,MINIO"),
+        ("access_key_id", "对象存储 access_key"),
+        ("secret_access_key", "对象存储 secret_access"),
+        ("endpoint", "对象存储endpoint，请参考供应商相关文档"),
+        ("region", "对象存储区域，请参考供应商相关文档"),
+        ("bucket", "对象存储 bucket"),
+        ("prefix", "对象存储prefix"),
+        ("check_option", "校验检查项"),
+        ("check_content_length", "文件长度校验"),
+        ("check_expires", "过期时间校验"),
+        ("check_content", "文件内容校验，既字节流校验"),
+        ("check_meta_data", "oss meta数据校验"),
+        ("attributes", "任务属性"),
+        (
+            "objects_per_batch",
+            "批次大小，既每批传输文件数据量，默认值 100",
+        ),
+        ("task_parallelism", "任务并行度，默认值为cpu核数"),
+        (
+            "max_errors",
+            "最大错误数，当错数量超过该值任务停止，默认值 1",
+        ),
+        (
+            "meta_dir",
+            "任务元数据保存目录，包括任务 checkpoint、错误记录文件、文件执行列表、增量文件列表等任务执行相关文件，默认值 /tmp/meta_dir",
+        ),
+        (
+            "target_exists_skip",
+            "当目标文件存在时是否同步，用以适应某些需要跳过重复同步的场景",
+        ),
+        (
+            "start_from_checkpoint",
+            "从 checkpoint 开始同步，用于任务中断后续传操作",
+        ),
+        (
+            "large_file_size",
+            "大文件拆分依据，当文件大于该属性值时，进行查分同步，默认值50M",
+        ),
+        ("multi_part_chunk_size", "拆分块尺寸，默认值 10M"),
+        ("multi_part_chunk", "拆分块尺寸，默认值 10M"),
+        (
+            "multi_part_chunks_per_batch",
+            "每批次上传的分块数量，默认值 10",
+        ),
+        (
+            "multi_part_parallelism",
+            "分块上传并行度，默认值为cpu核数+2，不超过最大并行度",
+        ),
+        (
+            "multi_part_max_parallelism",
+            "分块上传最大并行度，默认值为cpu核数*2",
+        ),
+        ("exclude", "正则表达式过滤，排除符合正则表达式的文件"),
+        ("include", "正则表达式过滤，包括符合正则表达式的文件"),
+        ("transfer_type", "传输类型：stock、increment、full"),
+        (
+            "exprirs_diff_scope",
+            "过期时间差值，允许差值之内的误差，默认值 10 秒",
+        ),
+        ("continuous", "存量模式完成后进入增量模式"),
+        ("last_modify_filter", "通过最后跟新时间过滤文件"),
+        (
+            "filter_type",
+            "过滤方式，指定大于或小于指定时间戳，Greater、Less",
+        ),
+        ("timestamp", "unix 时间戳"),
+    ])
+});
+
+// 离线模板类型，不依赖 server 生成任务模板
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfflineTemplate {
+    TransferOss2Oss,
+    TransferOss2Local,
+    TransferLocal2Oss,
+    TransferLocal2Local,
+    Compare,
+    TruncateBucket,
+}
+
+impl OfflineTemplate {
+    pub fn task(&self) -> Task {
+        let local_source = ObjectStorage::Local("/tmp/source_dir".to_string());
+        let local_target = ObjectStorage::Local("/tmp/target_dir".to_string());
+        let oss = ObjectStorage::OSS(OSSDescription::default());
+        // 源与目标均为对象存储时使用不同的目标 bucket，模板可直接通过校验
+        let target_oss = ObjectStorage::OSS(OSSDescription {
+            bucket: "target_bucket_name".to_string(),
+            ..Default::default()
+        });
+        match self {
+            OfflineTemplate::TransferOss2Oss => transfer_template(oss, target_oss),
+            OfflineTemplate::TransferOss2Local => transfer_template(oss, local_target),
+            OfflineTemplate::TransferLocal2Oss => transfer_template(local_source, oss),
+            OfflineTemplate::TransferLocal2Local => transfer_template(local_source, local_target),
+            OfflineTemplate::Compare => Task::Compare(CompareTask {
+                task_id: TaskDefaultParameters::id_default(),
+                name: TaskDefaultParameters::name_default(),
+                source: oss,
+                target: target_oss,
+                check_option: CompareCheckOption::default(),
+                attributes: CompareTaskAttributes::default(),
+            }),
+            OfflineTemplate::TruncateBucket => Task::TruncateBucket(TruncateTask {
+                task_id: TaskDefaultParameters::id_default(),
+                name: TaskDefaultParameters::name_default(),
+                target: OSSDescription::default(),
+                attributes: TruncateTaskAttributes::default(),
+            }),
+        }
+    }
+}
+
+fn transfer_template(source: ObjectStorage, target: ObjectStorage) -> Task {
+    Task::Transfer(TransferTask {
+        task_id: TaskDefaultParameters::id_default(),
+        name: TaskDefaultParameters::name_default(),
+        source,
+        target,
+        attributes: TransferTaskAttributes::default(),
+    })
+}
+
+// 为任务描述 yaml 添加属性说明注释
+pub fn task_yaml_with_comments(yaml: &str) -> String {
+    yaml_with_comments(yaml, &TASK_YAML_COMMENTS)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{task_yaml_with_comments, OfflineTemplate};
+    use crate::commons::yamlutile::{struct_to_yaml_string, yaml_documents_to_structs};
+    use crate::request::{Task, TaskType};
+
+    //cargo test request::task_template::test::test_offline_template -- --nocapture
+    #[test]
+    fn test_offline_template() {
+        let task = OfflineTemplate::TransferLocal2Oss.task();
+        let yaml = struct_to_yaml_string(&task).unwrap();
+        let commented = task_yaml_with_comments(&yaml);
+        println!("{}", commented);
+        assert!(commented.contains("# 任务属性\nattributes:"));
+        let parsed = yaml_documents_to_structs::<Task>(&commented).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].task_id(), task.task_id());
+
+        let truncate = OfflineTemplate::TruncateBucket.task();
+        let yaml = struct_to_yaml_string(&truncate).unwrap();
+        let parsed = yaml_documents_to_structs::<Task>(&yaml).unwrap();
+        assert_eq!(parsed[0].task_type(), TaskType::TruncateBucket);
+    }
+}
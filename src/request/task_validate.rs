@@ -0,0 +1,246 @@
+use super::{resolve_secret, CredentialField, ObjectStorage, Task};
+use crate::commons::RegexFilter;
+use std::ffi::CString;
+use std::fmt;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use url::Url;
+
+// 任务描述校验结果，path 为违规属性的 json 路径
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskViolation {
+    pub path: String,
+    pub message: String,
+}
+
+impl TaskViolation {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for TaskViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+// 在提交 server 前校验任务描述，返回全部违规项
+pub fn validate_task(task: &Task) -> Vec<TaskViolation> {
+    let mut violations = vec![];
+    match task {
+        Task::Transfer(t) => {
+            validate_storage(&t.source, "$.source", true, &mut violations);
+            validate_storage(&t.target, "$.target", false, &mut violations);
+            if t.source.eq(&t.target) {
+                violations.push(TaskViolation::new(
+                    "$.target",
+                    "source and target must be different",
+                ));
+            }
+            let attr = &t.attributes;
+            validate_regex(&attr.exclude, &attr.include, &mut violations);
+            if attr.multi_part_chunk_size > attr.large_file_size {
+                violations.push(TaskViolation::new(
+                    "$.attributes.multi_part_chunk_size",
+                    format!(
+                        "multi_part_chunk_size {} greater than large_file_size {}",
+                        attr.multi_part_chunk_size, attr.large_file_size
+                    ),
+                ));
+            }
+            if attr.multi_part_parallelism > attr.multi_part_max_parallelism {
+                violations.push(TaskViolation::new(
+                    "$.attributes.multi_part_parallelism",
+                    format!(
+                        "multi_part_parallelism {} greater than multi_part_max_parallelism {}",
+                        attr.multi_part_parallelism, attr.multi_part_max_parallelism
+                    ),
+                ));
+            }
+            validate_meta_dir(&attr.meta_dir, &mut violations);
+        }
+        Task::Compare(c) => {
+            validate_storage(&c.source, "$.source", true, &mut violations);
+            validate_storage(&c.target, "$.target", true, &mut violations);
+            if c.source.eq(&c.target) {
+                violations.push(TaskViolation::new(
+                    "$.target",
+                    "source and target must be different",
+                ));
+            }
+            let attr = &c.attributes;
+            validate_regex(&attr.exclude, &attr.include, &mut violations);
+            if attr.multi_part_chunk > attr.large_file_size {
+                violations.push(TaskViolation::new(
+                    "$.attributes.multi_part_chunk",
+                    format!(
+                        "multi_part_chunk {} greater than large_file_size {}",
+                        attr.multi_part_chunk, attr.large_file_size
+                    ),
+                ));
+            }
+            validate_meta_dir(&attr.meta_dir, &mut violations);
+        }
+        Task::TruncateBucket(t) => {
+            validate_storage(
+                &ObjectStorage::OSS(t.target.clone()),
+                "$.target",
+                true,
+                &mut violations,
+            );
+            let attr = &t.attributes;
+            validate_regex(&attr.exclude, &attr.include, &mut violations);
+            validate_meta_dir(&attr.meta_dir, &mut violations);
+        }
+    }
+    violations
+}
+
+// 本地源目录必须存在，本地目标目录允许由任务创建但上级目录需存在
+fn validate_storage(
+    storage: &ObjectStorage,
+    path: &str,
+    must_exist: bool,
+    violations: &mut Vec<TaskViolation>,
+) {
+    match storage {
+        ObjectStorage::Local(dir) => {
+            let p = Path::new(dir);
+            let exists = match must_exist {
+                true => p.is_dir(),
+                false => p.is_dir() || p.parent().is_some_and(|parent| parent.is_dir()),
+            };
+            if !exists {
+                violations.push(TaskViolation::new(
+                    path,
+                    format!("local path {} not exists", dir),
+                ));
+            }
+        }
+        ObjectStorage::OSS(oss) => {
+            if let Err(e) = Url::parse(&oss.endpoint) {
+                violations.push(TaskViolation::new(
+                    &format!("{}.endpoint", path),
+                    format!("invalid endpoint {}: {}", oss.endpoint, e),
+                ));
+            }
+            // 凭据引用需在提交前可解析
+            for (field, value) in [
+                (CredentialField::AccessKeyId, &oss.access_key_id),
+                (CredentialField::SecretAccessKey, &oss.secret_access_key),
+            ] {
+                if let Err(e) = resolve_secret(value, field) {
+                    violations.push(TaskViolation::new(
+                        &format!("{}.{}", path, field.name()),
+                        e.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn validate_regex(
+    exclude: &Option<Vec<String>>,
+    include: &Option<Vec<String>>,
+    violations: &mut Vec<TaskViolation>,
+) {
+    if let Err(e) = RegexFilter::from_vec(exclude, &None) {
+        violations.push(TaskViolation::new("$.attributes.exclude", e.to_string()));
+    }
+    if let Err(e) = RegexFilter::from_vec(&None, include) {
+        violations.push(TaskViolation::new("$.attributes.include", e.to_string()));
+    }
+}
+
+// meta_dir 不存在时检查最近的已存在上级目录是否可写
+fn validate_meta_dir(meta_dir: &str, violations: &mut Vec<TaskViolation>) {
+    let mut dir = Path::new(meta_dir);
+    while !dir.exists() {
+        match dir.parent() {
+            Some(p) if !p.as_os_str().is_empty() => dir = p,
+            _ => {
+                dir = Path::new(".");
+                break;
+            }
+        }
+    }
+
+    if !dir.is_dir() {
+        violations.push(TaskViolation::new(
+            "$.attributes.meta_dir",
+            format!("{} is not a directory", dir.display()),
+        ));
+        return;
+    }
+
+    // 按当前用户权限检查，不在用户目录中创建文件
+    let writable = CString::new(dir.as_os_str().as_bytes())
+        .map(|p| unsafe { libc::access(p.as_ptr(), libc::W_OK) } == 0)
+        .unwrap_or(false);
+    if !writable {
+        violations.push(TaskViolation::new(
+            "$.attributes.meta_dir",
+            format!("{} not writable", dir.display()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::validate_task;
+    use crate::request::{OSSDescription, ObjectStorage, OfflineTemplate, Task};
+
+    //cargo test request::task_validate::test::test_validate_task -- --nocapture
+    #[test]
+    fn test_validate_task() {
+        // 对象存储间的默认模板可直接通过校验
+        for template in [OfflineTemplate::TransferOss2Oss, OfflineTemplate::Compare] {
+            assert!(validate_task(&template.task()).is_empty());
+        }
+
+        let mut task = OfflineTemplate::TransferLocal2Local.task();
+        if let Task::Transfer(t) = &mut task {
+            t.source = ObjectStorage::Local("/tmp".to_string());
+            t.target = ObjectStorage::Local("/tmp/files_pipe_cli_target".to_string());
+        }
+        assert!(validate_task(&task).is_empty());
+
+        if let Task::Transfer(t) = &mut task {
+            t.target = t.source.clone();
+            t.attributes.include = Some(vec!["(".to_string()]);
+            t.attributes.multi_part_chunk_size = t.attributes.large_file_size + 1;
+        }
+        let violations = validate_task(&task);
+        let paths = violations
+            .iter()
+            .map(|v| v.path.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            paths,
+            vec![
+                "$.target",
+                "$.attributes.include",
+                "$.attributes.multi_part_chunk_size"
+            ]
+        );
+
+        let mut task = OfflineTemplate::TransferOss2Oss.task();
+        if let Task::Transfer(t) = &mut task {
+            t.source = ObjectStorage::OSS(OSSDescription {
+                secret_access_key: "env:FILES_PIPE_CLI_TEST_NOT_SET".to_string(),
+                bucket: "source".to_string(),
+                ..Default::default()
+            });
+        }
+        let paths = validate_task(&task)
+            .into_iter()
+            .map(|v| v.path)
+            .collect::<Vec<String>>();
+        assert_eq!(paths, vec!["$.source.secret_access_key"]);
+    }
+}
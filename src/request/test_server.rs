@@ -0,0 +1,48 @@
+// 单元测试使用的一次性 http server
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+// 读取一次请求，以 handler 根据请求原文返回的状态码及响应体应答
+pub fn respond<S, F>(stream: &mut S, handler: &mut F) -> io::Result<()>
+where
+    S: Read + Write,
+    F: FnMut(&str) -> (u16, String),
+{
+    let mut buf = [0; 4096];
+    let n = stream.read(&mut buf)?;
+    let (status, body) = handler(&String::from_utf8_lossy(&buf[..n]));
+    let resp = format!(
+        "HTTP/1.1 {} status\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(resp.as_bytes())
+}
+
+// 依次应答 times 次请求后退出
+pub fn serve<F>(listener: TcpListener, times: usize, mut handler: F) -> JoinHandle<()>
+where
+    F: FnMut(&str) -> (u16, String) + Send + 'static,
+{
+    thread::spawn(move || {
+        for _ in 0..times {
+            let (mut stream, _) = listener.accept().unwrap();
+            respond(&mut stream, &mut handler).unwrap();
+        }
+    })
+}
+
+// 依次以给定 data 应答请求
+pub fn serve_data(listener: TcpListener, data: Vec<&'static str>) -> JoinHandle<()> {
+    let mut data = data.into_iter();
+    serve(listener, data.len(), move |_| {
+        (200, ok_body(data.next().unwrap_or("null")))
+    })
+}
+
+// 成功应答的响应体
+pub fn ok_body(data: &str) -> String {
+    format!(r#"{{"code":0,"msg":"ok","data":{}}}"#, data)
+}
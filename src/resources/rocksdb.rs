@@ -1,17 +1,20 @@
 use crate::configure::CurrentSettings;
-use crate::request::TaskServer;
+use crate::request::{CredentialProfile, ServerAuth, ServerTls, TaskServer};
 use anyhow::anyhow;
 use anyhow::Context;
 use anyhow::Result;
+use bincode::Options as _;
 use once_cell::sync::Lazy;
 use rocksdb::IteratorMode;
 use rocksdb::{DBWithThreadMode, MultiThreaded, Options};
+use serde::{Deserialize, Serialize};
 use snowflake::SnowflakeIdGenerator;
 use std::sync::Arc;
 use url::Url;
 
 pub const CF_SERVERS: &'static str = "cf_servers";
 pub const CF_CURRENT_SETTITNGS: &'static str = "cf_current_settings";
+pub const CF_CREDENTIAL_PROFILES: &'static str = "cf_credential_profiles";
 
 pub const CURRENT_SETTITNGS_KEY: &'static str = "current_settings";
 
@@ -20,6 +23,10 @@ pub static GLOBAL_ROCKSDB: Lazy<Arc<DBWithThreadMode<MultiThreaded>>> = Lazy::ne
         Ok(db) => db,
         Err(err) => panic!("{}", err),
     };
+    // 迁移失败时仍可读取旧记录，不影响使用
+    if let Err(e) = migrate_task_servers(&rocksdb) {
+        log::error!("migrate servers: {:?}", e);
+    }
     Arc::new(rocksdb)
 });
 
@@ -42,6 +49,7 @@ pub fn init_rocksdb(db_path: &str) -> Result<DBWithThreadMode<MultiThreaded>> {
         vec![
             (CF_SERVERS, cf_opts.clone()),
             (CF_CURRENT_SETTITNGS, cf_opts.clone()),
+            (CF_CREDENTIAL_PROFILES, cf_opts.clone()),
         ],
     )?;
     Ok(db)
@@ -83,8 +91,7 @@ pub fn save_task_server_to_cf(task_server: &TaskServer) -> Result<i64> {
 
     let mut id_generator = SnowflakeIdGenerator::new(1, 1);
     let id = id_generator.real_time_generate();
-    let encoded: Vec<u8> =
-        bincode::serialize(task_server).context(format!("{}:{}", file!(), line!()))?;
+    let encoded = encode_task_server(task_server).context(format!("{}:{}", file!(), line!()))?;
     GLOBAL_ROCKSDB
         .put_cf(&cf, id.to_string().as_bytes(), encoded)
         .context(format!("{}:{}", file!(), line!()))?;
@@ -103,11 +110,8 @@ pub fn task_server_exist(task_server: &TaskServer) -> Result<bool> {
 
     for item in sever_cf_iter {
         if let Ok(kv) = item {
-            let task_server = bincode::deserialize::<TaskServer>(&kv.1).context(format!(
-                "{}:{}",
-                file!(),
-                line!()
-            ))?;
+            let (task_server, _) =
+                decode_task_server(&kv.1).context(format!("{}:{}", file!(), line!()))?;
             let saved_url =
                 Url::parse(&task_server.url).context(format!("{}:{}", file!(), line!()))?;
 
@@ -124,6 +128,115 @@ pub fn task_server_exist(task_server: &TaskServer) -> Result<bool> {
     Ok(saved)
 }
 
+// server 记录格式：4 字节头（SERVER_RECORD_MAGIC + 版本号）+ bincode 编码的对应版本结构。
+// 旧版本记录无头部，首 8 字节为 name 长度，以 0xff 开头时长度远超任何实际名称，不会与头部混淆
+const SERVER_RECORD_MAGIC: [u8; 3] = [0xff, b's', b'v'];
+const SERVER_RECORD_V0: u8 = 0;
+const SERVER_RECORD_V1: u8 = 1;
+const SERVER_RECORD_V2: u8 = 2;
+const SERVER_RECORD_VERSION: u8 = SERVER_RECORD_V2;
+
+// 仅包含 name 及 url 的版本
+#[derive(Serialize, Deserialize)]
+struct TaskServerV0 {
+    name: String,
+    url: String,
+}
+
+// 未包含 tls 配置的版本
+#[derive(Serialize, Deserialize)]
+struct TaskServerV1 {
+    name: String,
+    url: String,
+    auth: ServerAuth,
+}
+
+impl From<TaskServerV0> for TaskServer {
+    fn from(v0: TaskServerV0) -> Self {
+        TaskServer {
+            name: v0.name,
+            url: v0.url,
+            auth: ServerAuth::default(),
+            tls: ServerTls::default(),
+        }
+    }
+}
+
+impl From<TaskServerV1> for TaskServer {
+    fn from(v1: TaskServerV1) -> Self {
+        TaskServer {
+            name: v1.name,
+            url: v1.url,
+            auth: v1.auth,
+            tls: ServerTls::default(),
+        }
+    }
+}
+
+// 与 bincode::serialize 相同的编码，但要求字节恰好用完
+fn bincode_strict<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> bincode::Result<T> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+}
+
+fn encode_task_server(task_server: &TaskServer) -> Result<Vec<u8>> {
+    let mut encoded = SERVER_RECORD_MAGIC.to_vec();
+    encoded.push(SERVER_RECORD_VERSION);
+    encoded.extend(bincode::serialize(task_server)?);
+    Ok(encoded)
+}
+
+// 按版本号解码，返回 server 及记录是否需要迁移为当前版本
+fn decode_task_server(bytes: &[u8]) -> Result<(TaskServer, bool)> {
+    let (version, body) = match bytes.strip_prefix(&SERVER_RECORD_MAGIC) {
+        Some([version, body @ ..]) => (*version, body),
+        Some([]) => return Err(anyhow!("server record has no version")),
+        None => return Ok((decode_legacy_task_server(bytes)?, true)),
+    };
+    let task_server = match version {
+        SERVER_RECORD_V0 => bincode_strict::<TaskServerV0>(body)?.into(),
+        SERVER_RECORD_V1 => bincode_strict::<TaskServerV1>(body)?.into(),
+        SERVER_RECORD_V2 => bincode_strict::<TaskServer>(body)?,
+        v => return Err(anyhow!("unsupported server record version {}", v)),
+    };
+    Ok((task_server, version != SERVER_RECORD_VERSION))
+}
+
+// 无版本头的旧记录，各版本字段依次追加，按字节恰好用完的结构确定版本
+fn decode_legacy_task_server(bytes: &[u8]) -> Result<TaskServer> {
+    if let Ok(task_server) = bincode_strict::<TaskServer>(bytes) {
+        return Ok(task_server);
+    }
+    if let Ok(v1) = bincode_strict::<TaskServerV1>(bytes) {
+        return Ok(v1.into());
+    }
+    match bincode_strict::<TaskServerV0>(bytes) {
+        Ok(v0) => Ok(v0.into()),
+        Err(e) => Err(anyhow!("unknown server record layout: {}", e)),
+    }
+}
+
+// 将旧版本 server 记录改写为当前版本
+pub fn migrate_task_servers(db: &DBWithThreadMode<MultiThreaded>) -> Result<usize> {
+    let cf = match db.cf_handle(CF_SERVERS) {
+        Some(cf) => cf,
+        None => return Err(anyhow!("column family not exist")),
+    };
+    let mut migrated = 0;
+    for item in db.iterator_cf(&cf, IteratorMode::Start) {
+        let kv = item?;
+        let (task_server, outdated) = decode_task_server(&kv.1)
+            .context(format!("server {}", String::from_utf8_lossy(&kv.0)))?;
+        if outdated {
+            db.put_cf(&cf, &kv.0, encode_task_server(&task_server)?)?;
+            migrated += 1;
+        }
+    }
+    Ok(migrated)
+}
+
 pub fn get_task_server_from_cf(server_id: &str) -> Result<TaskServer> {
     let cf = match GLOBAL_ROCKSDB.cf_handle(CF_SERVERS) {
         Some(cf) => cf,
@@ -134,10 +247,31 @@ pub fn get_task_server_from_cf(server_id: &str) -> Result<TaskServer> {
         Some(v) => v,
         None => return Err(anyhow!("server not exists")),
     };
-    let task_server = bincode::deserialize::<TaskServer>(&task_server_bytes)?;
+    let (task_server, _) = decode_task_server(&task_server_bytes)?;
     Ok(task_server)
 }
 
+// 按 id 或名称查找 server，名称重复时返回错误
+pub fn find_task_server(id_or_name: &str) -> Result<(String, TaskServer)> {
+    if let Ok(task_server) = get_task_server_from_cf(id_or_name) {
+        return Ok((id_or_name.to_string(), task_server));
+    }
+
+    let mut matched = list_servers_from_cf()?
+        .into_iter()
+        .filter(|(_, task_server)| task_server.name.eq(id_or_name))
+        .collect::<Vec<(String, TaskServer)>>();
+
+    match matched.len() {
+        0 => Err(anyhow!("server {} not exists", id_or_name)),
+        1 => Ok(matched.remove(0)),
+        _ => Err(anyhow!(
+            "server name {} is ambiguous, use server id instead",
+            id_or_name
+        )),
+    }
+}
+
 pub fn remove_server_from_cf(server_id: &str) -> Result<()> {
     let cf = match GLOBAL_ROCKSDB.cf_handle(CF_SERVERS) {
         Some(cf) => cf,
@@ -159,7 +293,7 @@ pub fn list_servers_from_cf() -> Result<Vec<(String, TaskServer)>> {
     for item in sever_cf_iter {
         if let Ok(kv) = item {
             let key = String::from_utf8(kv.0.to_vec())?;
-            let task_server = bincode::deserialize::<TaskServer>(&kv.1)?;
+            let (task_server, _) = decode_task_server(&kv.1)?;
 
             vec_servers.push((key, task_server))
         }
@@ -167,3 +301,144 @@ pub fn list_servers_from_cf() -> Result<Vec<(String, TaskServer)>> {
 
     Ok(vec_servers)
 }
+
+pub fn save_credential_profile(name: &str, profile: &CredentialProfile) -> Result<()> {
+    let cf = match GLOBAL_ROCKSDB.cf_handle(CF_CREDENTIAL_PROFILES) {
+        Some(cf) => cf,
+        None => return Err(anyhow!("column family not exist")),
+    };
+    let encoded = bincode::serialize(profile)?;
+    GLOBAL_ROCKSDB.put_cf(&cf, name, encoded)?;
+    Ok(())
+}
+
+pub fn get_credential_profile(name: &str) -> Result<CredentialProfile> {
+    let cf = match GLOBAL_ROCKSDB.cf_handle(CF_CREDENTIAL_PROFILES) {
+        Some(cf) => cf,
+        None => return Err(anyhow!("column family not exist")),
+    };
+    let profile_bytes = match GLOBAL_ROCKSDB.get_cf(&cf, name)? {
+        Some(v) => v,
+        None => return Err(anyhow!("credential profile {} not exists", name)),
+    };
+    let profile = bincode::deserialize::<CredentialProfile>(&profile_bytes)?;
+    Ok(profile)
+}
+
+pub fn remove_credential_profile(name: &str) -> Result<()> {
+    let cf = match GLOBAL_ROCKSDB.cf_handle(CF_CREDENTIAL_PROFILES) {
+        Some(cf) => cf,
+        None => return Err(anyhow!("column family not exist")),
+    };
+    GLOBAL_ROCKSDB.delete_cf(&cf, name)?;
+    Ok(())
+}
+
+pub fn list_credential_profiles() -> Result<Vec<(String, CredentialProfile)>> {
+    let cf = match GLOBAL_ROCKSDB.cf_handle(CF_CREDENTIAL_PROFILES) {
+        Some(cf) => cf,
+        None => return Err(anyhow!("column family not exist")),
+    };
+
+    let mut profiles = vec![];
+    for item in GLOBAL_ROCKSDB.iterator_cf(&cf, IteratorMode::Start) {
+        let kv = item?;
+        let name = String::from_utf8(kv.0.to_vec())?;
+        let profile = bincode::deserialize::<CredentialProfile>(&kv.1)?;
+        profiles.push((name, profile));
+    }
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        decode_task_server, encode_task_server, migrate_task_servers, TaskServerV0, TaskServerV1,
+        CF_SERVERS, SERVER_RECORD_MAGIC, SERVER_RECORD_V0, SERVER_RECORD_V1,
+    };
+    use crate::request::{ServerAuth, ServerTls, TaskServer};
+    use rocksdb::{DBWithThreadMode, MultiThreaded};
+
+    fn task_server() -> TaskServer {
+        TaskServer {
+            name: "prod".to_string(),
+            url: "http://127.0.0.1:3000".to_string(),
+            auth: ServerAuth {
+                bearer_token: Some("token".to_string()),
+                ..Default::default()
+            },
+            tls: ServerTls {
+                ca_cert: Some("/etc/ca.pem".to_string()),
+                insecure: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn v0() -> TaskServerV0 {
+        TaskServerV0 {
+            name: "prod".to_string(),
+            url: "http://127.0.0.1:3000".to_string(),
+        }
+    }
+
+    fn v1() -> TaskServerV1 {
+        TaskServerV1 {
+            name: "prod".to_string(),
+            url: "http://127.0.0.1:3000".to_string(),
+            auth: task_server().auth,
+        }
+    }
+
+    fn versioned(version: u8, body: Vec<u8>) -> Vec<u8> {
+        let mut bytes = SERVER_RECORD_MAGIC.to_vec();
+        bytes.push(version);
+        bytes.extend(body);
+        bytes
+    }
+
+    //cargo test resources::rocksdb::test::test_decode_task_server -- --nocapture
+    #[test]
+    fn test_decode_task_server() {
+        let current = task_server();
+        let from_v0 = TaskServer {
+            auth: ServerAuth::default(),
+            tls: ServerTls::default(),
+            ..current.clone()
+        };
+        let from_v1 = TaskServer {
+            tls: ServerTls::default(),
+            ..current.clone()
+        };
+
+        // 当前版本
+        let bytes = encode_task_server(&current).unwrap();
+        assert_eq!(
+            decode_task_server(&bytes).unwrap(),
+            (current.clone(), false)
+        );
+
+        // 带版本头的旧版本
+        let bytes = versioned(SERVER_RECORD_V0, bincode::serialize(&v0()).unwrap());
+        assert_eq!(decode_task_server(&bytes).unwrap(), (from_v0.clone(), true));
+        let bytes = versioned(SERVER_RECORD_V1, bincode::serialize(&v1()).unwrap());
+        assert_eq!(decode_task_server(&bytes).unwrap(), (from_v1.clone(), true));
+
+        // 无版本头的旧记录
+        let bytes = bincode::serialize(&v0()).unwrap();
+        assert_eq!(decode_task_server(&bytes).unwrap(), (from_v0, true));
+        let bytes = bincode::serialize(&v1()).unwrap();
+        assert_eq!(decode_task_server(&bytes).unwrap(), (from_v1, true));
+        let bytes = bincode::serialize(&current).unwrap();
+        assert_eq!(decode_task_server(&bytes).unwrap(), (current.clone(), true));
+
+        // 未知版本及损坏的记录
+        let bytes = versioned(9, bincode::serialize(&current).unwrap());
+        assert!(decode_task_server(&bytes).is_err());
+        let mut bytes = encode_task_server(&current).unwrap();
+        bytes.push(0);
+        assert!(decode_task_server(&bytes).is_err());
+        let bytes = bincode::serialize(&current).unwrap();
+        assert!(decode_task_server(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -221,6 +221,7 @@ impl App {
                     KeyCode::Char('k') | KeyCode::Up => self.task_tab.prev(),
                     KeyCode::Char('j') | KeyCode::Down => self.task_tab.next(),
                     KeyCode::Char('f') => self.task_tab.refresh_data(),
+                    KeyCode::Char('l') => self.task_tab.switch_running_only(),
                     KeyCode::Char('c') => self.pop_task_editor.show_editor(),
                     KeyCode::Char('e') => {
                         let task_str = self.task_tab.get_task();
@@ -323,6 +324,13 @@ impl Widget for &App {
                 ("I".to_string(), "Stop Task".to_string()),
                 ("S".to_string(), "Task Status".to_string()),
                 ("D/Del".to_string(), "Del Task".to_string()),
+                (
+                    "L".to_string(),
+                    match self.task_tab.running_only() {
+                        true => "All Tasks".to_string(),
+                        false => "Running Only".to_string(),
+                    },
+                ),
                 ("Q/Esc".to_string(), "Quit".to_string()),
             ],
         };
@@ -29,6 +29,7 @@ const HELP: &'static [&'static str] = &[
     "K/↑: Up",
     "J/↓: Down",
     "F: Refresh task table",
+    "L: Show running tasks only",
     "C: Create task",
     "E: Edit task",
     "D: Delete task",
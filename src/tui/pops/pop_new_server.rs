@@ -1,4 +1,8 @@
-use crate::{request::TaskServer, resources::save_task_server_to_cf, tui::tabs::centered_rect};
+use crate::{
+    request::{ServerAuth, ServerTls, TaskServer},
+    resources::save_task_server_to_cf,
+    tui::tabs::centered_rect,
+};
 use once_cell::sync::Lazy;
 use ratatui::{
     layout::{Constraint, Layout},
@@ -7,7 +11,7 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Widget},
 };
 use std::sync::{Arc, RwLock};
-use strum::{EnumCount, EnumIter, FromRepr};
+use strum::{Display, EnumCount, EnumIter, FromRepr, IntoEnumIterator};
 use tui_textarea::{Input, TextArea};
 
 pub static GLOBAL_NEWSERVER_TEXT_AREA: Lazy<Arc<RwLock<NewServerTextArea>>> = Lazy::new(|| {
@@ -17,36 +21,58 @@ pub static GLOBAL_NEWSERVER_TEXT_AREA: Lazy<Arc<RwLock<NewServerTextArea>>> = La
 
 #[derive(Debug, Clone)]
 pub struct NewServerTextArea<'a> {
-    name_text_area: TextArea<'a>,
-    url_text_area: TextArea<'a>,
+    text_areas: Vec<TextArea<'a>>,
 }
 
 impl<'a> Default for NewServerTextArea<'a> {
     fn default() -> Self {
-        let mut name_text_area = TextArea::default();
-        let mut url_text_area = TextArea::default();
-
-        name_text_area.set_cursor_line_style(Style::default());
-        url_text_area.set_cursor_line_style(Style::default());
-
-        name_text_area.set_block(
-            Block::default()
-                .borders(Borders::ALL)
-                .style(Style::default()),
-        );
-        url_text_area.set_block(
-            Block::default()
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::DarkGray)),
-        );
+        let text_areas = SelectedInput::iter()
+            .map(|input| {
+                let mut text_area = TextArea::default();
+                text_area.set_cursor_line_style(Style::default());
+                text_area.set_placeholder_text(input.placeholder());
+                text_area
+            })
+            .collect::<Vec<TextArea>>();
+        let mut new_server_text_area = Self { text_areas };
+        new_server_text_area.focus(SelectedInput::default());
+        new_server_text_area
+    }
+}
 
-        name_text_area.set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
-        url_text_area.set_cursor_style(Style::default());
-        Self {
-            name_text_area,
-            url_text_area,
+impl<'a> NewServerTextArea<'a> {
+    // 高亮选中的输入框
+    fn focus(&mut self, selected: SelectedInput) {
+        for (idx, text_area) in self.text_areas.iter_mut().enumerate() {
+            let title = SelectedInput::from_repr(idx)
+                .unwrap_or_default()
+                .to_string();
+            match idx.eq(&(selected as usize)) {
+                true => {
+                    text_area.set_block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(title)
+                            .style(Style::default()),
+                    );
+                    text_area.set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+                }
+                false => {
+                    text_area.set_block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(title)
+                            .style(Style::default().fg(Color::DarkGray)),
+                    );
+                    text_area.set_cursor_style(Style::default());
+                }
+            }
         }
     }
+
+    fn value(&self, input: SelectedInput) -> String {
+        self.text_areas[input as usize].lines()[0].clone()
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -76,59 +102,30 @@ impl PopNewServer {
         self.selected_input =
             SelectedInput::from_repr(next_index).unwrap_or(SelectedInput::default());
 
-        let mut ns = GLOBAL_NEWSERVER_TEXT_AREA.write().unwrap();
-        match self.selected_input {
-            SelectedInput::Name => {
-                ns.name_text_area.set_block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .style(Style::default()),
-                );
-
-                ns.url_text_area.set_block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .style(Style::default().fg(Color::DarkGray)),
-                );
-
-                ns.name_text_area
-                    .set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
-                ns.url_text_area.set_cursor_style(Style::default());
-            }
-            SelectedInput::Url => {
-                ns.url_text_area.set_block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .style(Style::default()),
-                );
-
-                ns.name_text_area.set_block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .style(Style::default().fg(Color::DarkGray)),
-                );
-
-                ns.url_text_area
-                    .set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
-                ns.name_text_area.set_cursor_style(Style::default());
-            }
-        }
+        GLOBAL_NEWSERVER_TEXT_AREA
+            .write()
+            .unwrap()
+            .focus(self.selected_input);
     }
 
     pub fn add_server(&mut self) {
-        let name = GLOBAL_NEWSERVER_TEXT_AREA
-            .read()
-            .unwrap()
-            .name_text_area
-            .lines()[0]
-            .clone();
-        let url: String = GLOBAL_NEWSERVER_TEXT_AREA
-            .read()
-            .unwrap()
-            .url_text_area
-            .lines()[0]
-            .clone();
-        let task_server = TaskServer { name, url };
+        let ns = GLOBAL_NEWSERVER_TEXT_AREA.read().unwrap().clone();
+        let auth = match ServerAuth::from_spec(
+            &ns.value(SelectedInput::Auth),
+            &ns.value(SelectedInput::Headers),
+        ) {
+            Ok(a) => a,
+            Err(e) => {
+                self.alert_msg = e.to_string();
+                return;
+            }
+        };
+        let task_server = TaskServer {
+            name: ns.value(SelectedInput::Name),
+            url: ns.value(SelectedInput::Url),
+            auth,
+            tls: ServerTls::default(),
+        };
         self.clear();
         match save_task_server_to_cf(&task_server) {
             Ok(id) => self.alert_msg = format!("save task {} ok", id),
@@ -139,22 +136,8 @@ impl PopNewServer {
         };
     }
     pub fn input(&self, input: impl Into<Input>) {
-        match self.selected_input {
-            SelectedInput::Name => {
-                GLOBAL_NEWSERVER_TEXT_AREA
-                    .write()
-                    .unwrap()
-                    .name_text_area
-                    .input(input);
-            }
-            SelectedInput::Url => {
-                GLOBAL_NEWSERVER_TEXT_AREA
-                    .write()
-                    .unwrap()
-                    .url_text_area
-                    .input(input);
-            }
-        }
+        GLOBAL_NEWSERVER_TEXT_AREA.write().unwrap().text_areas[self.selected_input as usize]
+            .input(input);
     }
 }
 
@@ -163,37 +146,50 @@ impl Widget for PopNewServer {
     where
         Self: Sized,
     {
-        let editor_area = centered_rect(60, 30, area);
+        let editor_area = centered_rect(60, 50, area);
         let vertical = Layout::vertical([
-            Constraint::Percentage(10),
-            Constraint::Percentage(40),
-            Constraint::Percentage(40),
-            Constraint::Percentage(10),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(1),
         ]);
-        let [help_area, name_area, url_area, alert_area] = vertical.areas(editor_area);
+        let [help_area, name_area, url_area, auth_area, headers_area, alert_area] =
+            vertical.areas(editor_area);
         Clear.render(editor_area, buf);
-        let help = Text::from("pass 'Enter' key to add server,'ESC' to escape").centered();
+        let help =
+            Text::from("pass 'Enter' key to add server,'Tab' to switch input,'ESC' to escape")
+                .centered();
         help.render(help_area, buf);
-        GLOBAL_NEWSERVER_TEXT_AREA
-            .write()
-            .unwrap()
-            .name_text_area
-            .widget()
-            .render(name_area, buf);
-        GLOBAL_NEWSERVER_TEXT_AREA
-            .write()
-            .unwrap()
-            .url_text_area
-            .widget()
-            .render(url_area, buf);
+        let ns = GLOBAL_NEWSERVER_TEXT_AREA.read().unwrap();
+        for (input, input_area) in
+            SelectedInput::iter().zip([name_area, url_area, auth_area, headers_area])
+        {
+            (&ns.text_areas[input as usize]).render(input_area, buf);
+        }
         let alert_msg = Text::from(self.alert_msg.as_str()).centered();
         alert_msg.render(alert_area, buf);
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter, FromRepr, EnumCount)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumIter, FromRepr, EnumCount)]
+#[strum(serialize_all = "lowercase")]
 pub enum SelectedInput {
     #[default]
     Name,
     Url,
+    Auth,
+    Headers,
+}
+
+impl SelectedInput {
+    fn placeholder(&self) -> &'static str {
+        match self {
+            SelectedInput::Name => "server name",
+            SelectedInput::Url => "http://127.0.0.1:3000",
+            SelectedInput::Auth => "bearer <token> | basic <user>:<password>, optional",
+            SelectedInput::Headers => "Name: value; Name2: value2, optional",
+        }
+    }
 }
@@ -91,14 +91,7 @@ impl PopSelectTemplate {
             let task = match resp_task {
                 Ok(t) => t,
                 Err(e) => {
-                    log::error!("{:?}", e);
-                    return;
-                }
-            };
-
-            let task = match task.data {
-                Some(t) => t,
-                None => {
+                    log::error!("{}", e);
                     return;
                 }
             };
@@ -1,6 +1,9 @@
 use crate::{
     commons::json_to_struct,
-    request::{task_create, Task, GLOBAL_RUNTIME},
+    request::{
+        has_masked_secrets, resolve_task_secrets, task_create, task_show, unmask_task_secrets,
+        Task, TaskId, GLOBAL_RUNTIME,
+    },
     tui::tabs::centered_rect,
 };
 use anyhow::anyhow;
@@ -56,7 +59,7 @@ impl PopTaskEditor {
         }
 
         GLOBAL_RUNTIME.block_on(async move {
-            let task = match json_to_struct::<Task>(&task_json) {
+            let mut task = match json_to_struct::<Task>(&task_json) {
                 Ok(t) => t,
                 Err(e) => {
                     log::error!("{}", e);
@@ -65,18 +68,34 @@ impl PopTaskEditor {
                 }
             };
 
-            let task = match task_create(&task).await {
+            // 编辑已有任务时凭据被隐藏，按任务 id 取回原凭据
+            if has_masked_secrets(&task) {
+                let id = TaskId {
+                    task_id: task.task_id(),
+                };
+                match task_show(&id).await {
+                    Ok(original) => unmask_task_secrets(&mut task, &original),
+                    Err(e) => {
+                        log::error!("{}", e);
+                        *is_error_mut = true;
+                        return;
+                    }
+                }
+            }
+
+            let task = match resolve_task_secrets(&task) {
                 Ok(t) => t,
                 Err(e) => {
-                    log::error!("{:?}", e);
+                    log::error!("{}", e);
                     *is_error_mut = true;
                     return;
                 }
             };
 
-            let task = match task.data {
-                Some(t) => t,
-                None => {
+            let task = match task_create(&task).await {
+                Ok(t) => t,
+                Err(e) => {
+                    log::error!("{}", e);
                     *is_error_mut = true;
                     return;
                 }
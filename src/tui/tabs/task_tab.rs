@@ -1,8 +1,8 @@
 use crate::{
-    commons::{struct_to_json_string, struct_to_json_string_prettry},
+    commons::struct_to_json_string_prettry,
     request::{
-        list_all_tasks, task_remove, task_show, task_start, task_status, task_stop, Task, TaskId,
-        GLOBAL_RUNTIME,
+        list_all_tasks, mask_task_secrets, task_all_living, task_remove, task_show, task_start,
+        task_status, task_stop, Task, TaskId, TaskStatus, GLOBAL_RUNTIME,
     },
     tui::pops::PopAlert,
 };
@@ -18,7 +18,7 @@ use ratatui::{
         StatefulWidget, Table, TableState, Widget,
     },
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use unicode_width::UnicodeWidthStr;
 
 // use crate::{RgbSwatch, THEME};
@@ -78,6 +78,8 @@ pub struct TaskTab {
     colors: TableColors,
     color_index: usize,
     longest_item_lens: (u16, u16, u16, u16),
+    // 只显示启动中或运行中的任务
+    running_only: bool,
     pub pop_alert: PopAlert,
 }
 
@@ -105,6 +107,16 @@ impl TaskTab {
         };
     }
 
+    pub fn switch_running_only(&mut self) {
+        self.running_only = !self.running_only;
+        self.row_index = 0;
+        self.refresh_data();
+    }
+
+    pub fn running_only(&self) -> bool {
+        self.running_only
+    }
+
     pub fn set_colors(&mut self) {
         self.colors = TableColors::new(&PALETTES[self.color_index]);
     }
@@ -115,22 +127,15 @@ impl TaskTab {
         let t_j = Arc::get_mut(&mut task_json).unwrap();
         GLOBAL_RUNTIME.block_on(async move {
             let id = TaskId { task_id };
-            let resp_task = match task_show(&id).await {
+            let t = match task_show(&id).await {
                 Ok(t) => t,
                 Err(e) => {
-                    log::error!("{:?}", e);
-                    return;
-                }
-            };
-
-            let t = match resp_task.data {
-                Some(t) => t,
-                None => {
+                    log::error!("{}", e);
                     return;
                 }
             };
 
-            let task_json = match struct_to_json_string_prettry(&t) {
+            let task_json = match struct_to_json_string_prettry(&mask_task_secrets(&t)) {
                 Ok(j) => j,
                 Err(e) => {
                     log::error!("{:?}", e);
@@ -149,18 +154,9 @@ impl TaskTab {
         let e_e = Arc::get_mut(&mut exec_err).unwrap();
         GLOBAL_RUNTIME.block_on(async move {
             let req_id = TaskId { task_id };
-            let resp = match task_start(&req_id).await {
-                Ok(t) => t,
-                Err(e) => {
-                    *e_e = Some(e.to_string());
-                    log::error!("{:?}", e);
-                    return;
-                }
-            };
-
-            if !resp.code.eq(&0) {
-                let resp_str = struct_to_json_string(&resp).unwrap();
-                *e_e = Some(resp_str);
+            if let Err(e) = task_start(&req_id).await {
+                *e_e = Some(e.to_string());
+                log::error!("{}", e);
             }
         });
         match exec_err.as_deref() {
@@ -180,19 +176,9 @@ impl TaskTab {
         let e_e = Arc::get_mut(&mut exec_err).unwrap();
         GLOBAL_RUNTIME.block_on(async move {
             let req_id = TaskId { task_id };
-            let resp = match task_stop(&req_id).await {
-                Ok(t) => t,
-                Err(e) => {
-                    *e_e = Some(e.to_string());
-                    log::error!("{:?}", e);
-                    return;
-                }
-            };
-            log::info!("{:?}", resp);
-
-            if !resp.code.eq(&0) {
-                let resp_str = struct_to_json_string(&resp).unwrap();
-                *e_e = Some(resp_str);
+            if let Err(e) = task_stop(&req_id).await {
+                *e_e = Some(e.to_string());
+                log::error!("{}", e);
             }
         });
         match exec_err.as_deref() {
@@ -226,34 +212,54 @@ impl TaskTab {
 
     pub fn refresh_data(&mut self) {
         GLOBAL_TASKS_LIST.clear();
+        let running_only = self.running_only;
         GLOBAL_RUNTIME.block_on(async move {
-            let reps = match list_all_tasks().await {
-                Ok(r) => r,
+            let tasks = match list_all_tasks().await {
+                Ok(t) => t,
                 Err(e) => {
-                    log::error!("{:?}", e);
+                    log::error!("{}", e);
                     return;
                 }
             };
-            let tasks = match reps.data {
-                Some(v) => v,
-                None => return,
+
+            // 仅显示运行中任务时一次获取全部运行中任务状态
+            let living = match running_only {
+                true => match task_all_living().await {
+                    Ok(l) => Some(
+                        l.into_iter()
+                            .map(|s| (s.task_id.clone(), s))
+                            .collect::<HashMap<String, TaskStatus>>(),
+                    ),
+                    Err(e) => {
+                        log::error!("{}", e);
+                        return;
+                    }
+                },
+                false => None,
             };
 
             // let mut builder = Builder::default();
             for resp_task in tasks {
-                let status = match task_status(&TaskId {
-                    task_id: resp_task.task.task_id(),
-                })
-                .await
-                {
-                    Ok(t_s) => match t_s.data {
-                        Some(s) => s.status.to_string(),
-                        None => "stopped".to_string(),
+                let task_status = match &living {
+                    Some(l) => match l.get(&resp_task.task.task_id()) {
+                        Some(s) => Some(s.clone()),
+                        None => continue,
                     },
-                    Err(e) => {
-                        log::error!("{:?}", e);
-                        "stopped".to_string()
-                    }
+                    None => match task_status(&TaskId {
+                        task_id: resp_task.task.task_id(),
+                    })
+                    .await
+                    {
+                        Ok(t_s) => t_s,
+                        Err(e) => {
+                            log::error!("{:?}", e);
+                            None
+                        }
+                    },
+                };
+                let status = match task_status {
+                    Some(s) => s.status.to_string(),
+                    None => "stopped".to_string(),
                 };
                 let task_row = TaskRow {
                     id: resp_task.task.task_id(),
@@ -0,0 +1,26 @@
+#!/bin/sh
+# 生成测试用 CA、server 及 client 证书，私钥为 PKCS#8 格式，仅用于单元测试
+set -e
+cd "$(dirname "$0")"
+
+openssl req -x509 -newkey rsa:2048 -nodes -days 36500 \
+    -keyout ca.key -out ca.pem -subj "/CN=files_pipe_cli test ca" \
+    -addext "basicConstraints=critical,CA:TRUE" \
+    -addext "keyUsage=critical,keyCertSign,cRLSign"
+
+issue() {
+    name=$1
+    ext=$2
+    openssl req -newkey rsa:2048 -nodes -keyout "$name.key" -out "$name.csr" -subj "/CN=$name"
+    printf "basicConstraints=CA:FALSE\n%s\n" "$ext" > "$name.ext"
+    openssl x509 -req -in "$name.csr" -CA ca.pem -CAkey ca.key -CAcreateserial \
+        -days 36500 -out "$name.pem" -extfile "$name.ext"
+    rm "$name.csr" "$name.ext"
+}
+
+issue server "extendedKeyUsage=serverAuth
+subjectAltName=DNS:localhost,IP:127.0.0.1"
+issue client "extendedKeyUsage=clientAuth"
+
+# CA 私钥不再需要，不提交
+rm -f ca.key ca.srl